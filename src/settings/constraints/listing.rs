@@ -1,5 +1,6 @@
 use crate::settings;
 use crate::shapes;
+use itertools::Itertools;
 
 pub fn list_constraints(
     setting: &settings::PuzzleSetting,
@@ -15,53 +16,874 @@ pub fn list_constraints(
                 let constraints = iter_sudoku_constraints(setting, *tokenset, regions, givens)?;
                 result.extend(constraints);
             }
+            settings::Rule::Sandwich { tokenset, clues } => {
+                let constraints = iter_sandwich_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::LittleKiller { tokenset, clues } => {
+                let constraints = iter_little_killer_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::AntiChess { tokenset, moves } => {
+                let constraints = iter_anti_chess_constraints(setting, *tokenset, moves)?;
+                result.extend(constraints);
+            }
+            settings::Rule::NonConsecutive { tokenset } => {
+                let constraints = iter_non_consecutive_constraints(setting, *tokenset)?;
+                result.extend(constraints);
+            }
+            settings::Rule::CellRestriction {
+                tokenset,
+                restrictions,
+            } => {
+                let constraints = iter_restriction_constraints(setting, *tokenset, restrictions)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Inequality { tokenset, clues } => {
+                let constraints = iter_inequality_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Line {
+                tokenset,
+                kind,
+                path,
+            } => {
+                let constraints = iter_line_constraints(setting, *tokenset, *kind, path)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Skyscrapers { tokenset, clues } => {
+                let constraints = iter_skyscrapers_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::XSums { tokenset, clues } => {
+                let constraints = iter_x_sums_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::FirstSums {
+                tokenset,
+                count,
+                clues,
+            } => {
+                let constraints = iter_first_sums_constraints(setting, *tokenset, *count, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::OutsideSymbols {
+                tokenset,
+                count,
+                clues,
+            } => {
+                let constraints =
+                    iter_outside_symbols_constraints(setting, *tokenset, *count, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Quadruple { tokenset, clues } => {
+                let constraints = iter_quadruple_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Shading { tokenset, givens } => {
+                let constraints = iter_shading_constraints(setting, *tokenset, givens)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Edges { tokenset, givens } => {
+                let constraints = iter_edge_constraints(setting, *tokenset, givens)?;
+                result.extend(constraints);
+            }
+            settings::Rule::SingleLoop { tokenset } => {
+                let (grid, lattice) = get_edgeset(setting, *tokenset)?;
+                result.push(settings::Constraint::SingleLoop {
+                    tokenset: *tokenset,
+                    edges: shapes::Edge::all(grid, lattice),
+                });
+            }
+            settings::Rule::StarBattle {
+                tokenset,
+                stars_per_unit,
+                regions,
+            } => {
+                let constraints =
+                    iter_star_battle_constraints(setting, *tokenset, *stars_per_unit, regions)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Nurikabe { tokenset, clues } => {
+                let constraints = iter_nurikabe_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::Kakuro { tokenset, clues } => {
+                let constraints = iter_kakuro_constraints(setting, *tokenset, clues)?;
+                result.extend(constraints);
+            }
+            settings::Rule::SharedCells {
+                tokenset,
+                other_tokenset,
+                cells,
+            } => {
+                let constraints =
+                    iter_shared_cells_constraints(setting, *tokenset, *other_tokenset, cells)?;
+                result.extend(constraints);
+            }
         }
     }
     Ok(result)
 }
 
-fn iter_sudoku_constraints(
+fn get_tokenset(
     setting: &settings::PuzzleSetting,
     tokenset_index: settings::TokenSetIndex,
-    regions: &[shapes::Region],
-    givens: &[settings::GivenSymbol],
-) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
-    let tokenset = setting
+) -> Result<&settings::TokenSet, Box<dyn std::error::Error>> {
+    Ok(setting
         .tokensets
-        .get(tokenset_index as usize)
-        .ok_or("tokenset index out of range")?;
-    let grid_index;
-    let repartition;
-    match tokenset {
-        settings::TokenSet::Symbols { grid, candidates } => {
-            grid_index = grid;
-            repartition = candidates
-                .chars()
-                .map(|symbol| (symbol, 1))
-                .collect::<std::collections::HashMap<_, _>>();
-        }
+        .get(tokenset_index)
+        .ok_or("tokenset index out of range")?)
+}
+
+fn get_symbolset(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+) -> Result<(&settings::Grid, &String), Box<dyn std::error::Error>> {
+    let tokenset = get_tokenset(setting, tokenset_index)?;
+    let (grid_index, candidates) = match tokenset {
+        settings::TokenSet::Symbols {
+            grid, candidates, ..
+        } => (grid, candidates),
+        _ => return Err("expected a symbol tokenset".into()),
+    };
+    let grid = setting
+        .grids
+        .get(*grid_index)
+        .ok_or("grid index out of range")?;
+    Ok((grid, candidates))
+}
+
+fn get_shadeset(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+) -> Result<&settings::Grid, Box<dyn std::error::Error>> {
+    let grid_index = match get_tokenset(setting, tokenset_index)? {
+        settings::TokenSet::Shading { grid } => grid,
+        _ => return Err("expected a shading tokenset".into()),
+    };
+    Ok(setting
+        .grids
+        .get(*grid_index)
+        .ok_or("grid index out of range")?)
+}
+
+fn get_edgeset(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+) -> Result<(&settings::Grid, shapes::Lattice), Box<dyn std::error::Error>> {
+    let (grid_index, lattice) = match get_tokenset(setting, tokenset_index)? {
+        settings::TokenSet::Edges { grid, lattice } => (grid, *lattice),
+        _ => return Err("expected an edge tokenset".into()),
     };
     let grid = setting
         .grids
-        .get(*grid_index as usize)
+        .get(*grid_index)
         .ok_or("grid index out of range")?;
-    let rows = shapes::Region::rows(grid.rows, grid.columns);
-    let columns = shapes::Region::columns(grid.columns, grid.rows);
-    let all_regions = regions.iter().cloned().chain(rows).chain(columns);
-    let repartition_constraints =
-        all_regions.map(move |region| settings::Constraint::SymbolRepartition {
+    Ok((grid, lattice))
+}
+
+fn outside_region(
+    grid: &settings::Grid,
+    clue: &settings::OutsideClue,
+) -> Result<shapes::Region, Box<dyn std::error::Error>> {
+    let region = match clue.side {
+        settings::Side::Left | settings::Side::Right => shapes::Region::rows(grid).nth(clue.index),
+        settings::Side::Top | settings::Side::Bottom => {
+            shapes::Region::columns(grid).nth(clue.index)
+        }
+    };
+    let mut region = region.ok_or("clue index out of range")?;
+    if let settings::Side::Right | settings::Side::Bottom = clue.side {
+        region.cells.reverse();
+    }
+    Ok(region)
+}
+
+fn outside_prefix(
+    grid: &settings::Grid,
+    side: settings::Side,
+    index: usize,
+    count: usize,
+) -> Result<shapes::Region, Box<dyn std::error::Error>> {
+    let clue = settings::OutsideClue {
+        side,
+        index,
+        value: count,
+    };
+    let mut region = outside_region(grid, &clue)?;
+    if count > region.cells.len() {
+        return Err("clue count larger than the grid".into());
+    }
+    region.cells.truncate(count);
+    Ok(region)
+}
+
+fn iter_sudoku_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    regions: &[shapes::Region],
+    givens: &[settings::GivenSymbol],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, candidates) = get_symbolset(setting, tokenset_index)?;
+    let repartition = candidates
+        .chars()
+        .map(|symbol| (symbol, 1))
+        .collect::<std::collections::HashMap<_, _>>();
+    let lines = shapes::Region::lines(grid);
+    let regions = regions.iter().map(|region| shapes::Region {
+        cells: region
+            .cells
+            .iter()
+            .filter(|cell| grid.contains(cell))
+            .cloned()
+            .collect(),
+    });
+    let all_regions = regions.chain(lines);
+    let mut result = Vec::new();
+    for region in all_regions {
+        if region.cells.len() > repartition.len() {
+            return Err("region larger than the symbol count".into());
+        }
+        if region.cells.len() == repartition.len() {
+            result.push(settings::Constraint::SymbolRepartition {
+                tokenset: tokenset_index,
+                repartition: repartition.clone(),
+                region,
+            });
+            continue;
+        }
+        // Regions broken by missing cells only need distinct symbols.
+        for (index, first) in region.cells.iter().enumerate() {
+            for second in &region.cells[index + 1..] {
+                result.push(settings::Constraint::Different {
+                    tokenset: tokenset_index,
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+    }
+    for given in givens {
+        if !grid.contains(&given.cell) {
+            return Err("given symbol outside of grid".into());
+        }
+        if !candidates.contains(given.symbol) {
+            return Err(format!("given symbol {} not in tokenset", given.symbol).into());
+        }
+        result.push(settings::Constraint::GivenSymbol {
             tokenset: tokenset_index,
-            repartition: repartition.clone(),
-            region,
+            cell: given.cell.clone(),
+            symbol: given.symbol,
         });
-    let given_contraints = givens
+    }
+    Ok(result)
+}
+
+fn iter_shading_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    givens: &[settings::GivenShade],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let grid = get_shadeset(setting, tokenset_index)?;
+    givens
+        .iter()
+        .map(|given| {
+            if !grid.contains(&given.cell) {
+                return Err("given shade outside of grid".into());
+            }
+            Ok(settings::Constraint::GivenShade {
+                tokenset: tokenset_index,
+                cell: given.cell.clone(),
+                shaded: given.shaded,
+            })
+        })
+        .collect()
+}
+
+fn iter_edge_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    givens: &[settings::GivenEdge],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, lattice) = get_edgeset(setting, tokenset_index)?;
+    let edges = shapes::Edge::all(grid, lattice);
+    givens
         .iter()
-        .map(move |given| settings::Constraint::GivenSymbol {
+        .map(|given| {
+            if !edges.contains(&given.edge) {
+                return Err("given edge outside of grid".into());
+            }
+            Ok(settings::Constraint::GivenEdge {
+                tokenset: tokenset_index,
+                edge: given.edge.clone(),
+                on: given.on,
+            })
+        })
+        .collect()
+}
+
+fn iter_star_battle_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    stars_per_unit: usize,
+    regions: &[shapes::Region],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let grid = get_shadeset(setting, tokenset_index)?;
+    let units = shapes::Region::rows(grid)
+        .chain(shapes::Region::columns(grid))
+        .chain(regions.iter().cloned());
+    let mut result = Vec::new();
+    for region in units {
+        if region.cells.iter().any(|cell| !grid.contains(cell)) {
+            return Err("region cell outside of grid".into());
+        }
+        result.push(settings::Constraint::ShadeCount {
             tokenset: tokenset_index,
-            cell: given.cell.clone(),
-            symbol: given.symbol,
+            region,
+            count: stars_per_unit,
+        });
+    }
+    let no_touch = shapes::Moves::King
+        .pairs(grid)
+        .into_iter()
+        .map(|(first, second)| settings::Constraint::NotBothShaded {
+            tokenset: tokenset_index,
+            first,
+            second,
+        });
+    result.extend(no_touch);
+    Ok(result)
+}
+
+fn iter_nurikabe_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::IslandClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let grid = get_shadeset(setting, tokenset_index)?;
+    if grid.geometry != shapes::Geometry::Square {
+        return Err("nurikabe expects a square geometry".into());
+    }
+    let mut result = Vec::new();
+    for clue in clues {
+        if !grid.contains(&clue.cell) || clue.size == 0 {
+            return Err("invalid island clue".into());
+        }
+        result.push(settings::Constraint::GivenShade {
+            tokenset: tokenset_index,
+            cell: clue.cell.clone(),
+            shaded: false,
         });
-    Ok(repartition_constraints.chain(given_contraints).collect())
+    }
+    let region = shapes::Region {
+        cells: shapes::Region::rows(grid)
+            .flat_map(|row| row.cells)
+            .collect(),
+    };
+    let adjacency = shapes::Moves::Orthogonal.pairs(grid);
+    result.push(settings::Constraint::ShadedConnected {
+        tokenset: tokenset_index,
+        region: region.clone(),
+        adjacency: adjacency.clone(),
+    });
+    result.push(settings::Constraint::NumberedIslands {
+        tokenset: tokenset_index,
+        region: region.clone(),
+        adjacency,
+        clues: clues
+            .iter()
+            .map(|clue| (clue.cell.clone(), clue.size))
+            .collect(),
+    });
+    let blocks = region.cells.iter().filter_map(|cell| {
+        let cells = vec![
+            cell.clone(),
+            cell.offset(1, 0, grid)?,
+            cell.offset(0, 1, grid)?,
+            cell.offset(1, 1, grid)?,
+        ];
+        Some(settings::Constraint::NotAllShaded {
+            tokenset: tokenset_index,
+            region: shapes::Region { cells },
+        })
+    });
+    result.extend(blocks);
+    Ok(result)
+}
+
+fn iter_kakuro_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::KakuroClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+    let mut result = Vec::new();
+    for clue in clues {
+        let shapes::Cell(x, y) = clue.cell;
+        if x >= grid.columns || y >= grid.rows || grid.contains(&clue.cell) {
+            return Err("kakuro clue must be on a masked cell".into());
+        }
+        let runs = [(clue.across, (1, 0)), (clue.down, (0, 1))];
+        for (sum, (dx, dy)) in runs {
+            let Some(sum) = sum else {
+                continue;
+            };
+            let cells = (1..)
+                .map(|step| shapes::Cell(x + step * dx, y + step * dy))
+                .take_while(|cell| grid.contains(cell))
+                .collect::<Vec<_>>();
+            if cells.is_empty() {
+                return Err("kakuro clue has no run".into());
+            }
+            result.push(settings::Constraint::RegionSum {
+                tokenset: tokenset_index,
+                region: shapes::Region { cells },
+                values: values.clone(),
+                sum,
+                distinct: true,
+            });
+        }
+    }
+    Ok(result)
+}
+
+fn iter_sandwich_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::OutsideClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+    let order = get_tokenset(setting, tokenset_index)?.symbol_order();
+    let smallest = order.iter().min_by_key(|(_, rank)| **rank);
+    let largest = order.iter().max_by_key(|(_, rank)| **rank);
+    let (smallest, largest) = match (smallest, largest) {
+        (Some((smallest, _)), Some((largest, _))) => (*smallest, *largest),
+        _ => return Err("empty tokenset".into()),
+    };
+    clues
+        .iter()
+        .map(|clue| {
+            Ok(settings::Constraint::Sandwich {
+                tokenset: tokenset_index,
+                region: outside_region(grid, clue)?,
+                crusts: (smallest, largest),
+                values: values.clone(),
+                sum: clue.value,
+            })
+        })
+        .collect()
+}
+
+fn iter_little_killer_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::LittleKillerClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+    clues
+        .iter()
+        .map(|clue| {
+            let region = shapes::Region::diagonal(grid, &clue.cell, clue.direction);
+            if region.cells.is_empty() {
+                return Err("clue cell out of grid".into());
+            }
+            Ok(settings::Constraint::RegionSum {
+                tokenset: tokenset_index,
+                region,
+                values: values.clone(),
+                sum: clue.sum,
+                distinct: false,
+            })
+        })
+        .collect()
+}
+
+fn iter_anti_chess_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    moves: &shapes::Moves,
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    Ok(moves
+        .pairs(grid)
+        .into_iter()
+        .map(|(first, second)| settings::Constraint::Different {
+            tokenset: tokenset_index,
+            first,
+            second,
+        })
+        .collect())
+}
+
+fn iter_non_consecutive_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let order = get_tokenset(setting, tokenset_index)?.symbol_order();
+    Ok(shapes::Moves::Orthogonal
+        .pairs(grid)
+        .into_iter()
+        .map(|(first, second)| settings::Constraint::NonConsecutive {
+            tokenset: tokenset_index,
+            first,
+            second,
+            order: order.clone(),
+        })
+        .collect())
+}
+
+fn iter_restriction_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    restrictions: &[settings::CellRestriction],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (_, candidates) = get_symbolset(setting, tokenset_index)?;
+    let order = get_tokenset(setting, tokenset_index)?.symbol_order();
+    let half = order.len() / 2;
+    restrictions
+        .iter()
+        .map(|restriction| {
+            let symbols = match &restriction.restriction {
+                settings::Restriction::Even | settings::Restriction::Odd => {
+                    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+                    let remainder = match restriction.restriction {
+                        settings::Restriction::Even => 0,
+                        _ => 1,
+                    };
+                    candidates
+                        .chars()
+                        .filter(|symbol| values[symbol] % 2 == remainder)
+                        .collect()
+                }
+                settings::Restriction::Low => candidates
+                    .chars()
+                    .filter(|symbol| order[symbol] < half)
+                    .collect(),
+                settings::Restriction::High => candidates
+                    .chars()
+                    .filter(|symbol| order[symbol] >= order.len() - half)
+                    .collect(),
+                settings::Restriction::Symbols { symbols } => candidates
+                    .chars()
+                    .filter(|symbol| symbols.contains(*symbol))
+                    .collect(),
+            };
+            Ok(settings::Constraint::AllowedSymbols {
+                tokenset: tokenset_index,
+                cell: restriction.cell.clone(),
+                symbols,
+            })
+        })
+        .collect()
+}
+
+fn iter_inequality_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::Inequality],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let order = get_tokenset(setting, tokenset_index)?.symbol_order();
+    let adjacent_pairs = shapes::Moves::Orthogonal.pairs(grid);
+    clues
+        .iter()
+        .map(|clue| {
+            let pair = if clue.lower < clue.greater {
+                (clue.lower.clone(), clue.greater.clone())
+            } else {
+                (clue.greater.clone(), clue.lower.clone())
+            };
+            if !adjacent_pairs.contains(&pair) {
+                return Err("inequality cells are not adjacent".into());
+            }
+            Ok(settings::Constraint::Less {
+                tokenset: tokenset_index,
+                lower: clue.lower.clone(),
+                greater: clue.greater.clone(),
+                order: order.clone(),
+            })
+        })
+        .collect()
+}
+
+fn iter_line_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    kind: settings::LineKind,
+    path: &shapes::Path,
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    path.validate()?;
+    let (grid, candidates) = get_symbolset(setting, tokenset_index)?;
+    if path.cells.iter().any(|cell| !grid.contains(cell)) {
+        return Err("path cell outside of grid".into());
+    }
+    let constraints = match kind {
+        settings::LineKind::Renban => vec![settings::Constraint::Renban {
+            tokenset: tokenset_index,
+            region: shapes::Region {
+                cells: path.cells.clone(),
+            },
+            order: get_tokenset(setting, tokenset_index)?.symbol_order(),
+        }],
+        settings::LineKind::GermanWhispers | settings::LineKind::DutchWhispers => {
+            let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+            let difference = match kind {
+                settings::LineKind::GermanWhispers => 5,
+                _ => 4,
+            };
+            path.cells
+                .windows(2)
+                .map(|pair| settings::Constraint::MinDifference {
+                    tokenset: tokenset_index,
+                    first: pair[0].clone(),
+                    second: pair[1].clone(),
+                    values: values.clone(),
+                    difference,
+                })
+                .collect()
+        }
+        settings::LineKind::Palindrome => path
+            .cells
+            .iter()
+            .zip(path.cells.iter().rev())
+            .take(path.cells.len() / 2)
+            .map(|(first, second)| settings::Constraint::SameSymbol {
+                tokenset: tokenset_index,
+                first: first.clone(),
+                second: second.clone(),
+            })
+            .collect(),
+        settings::LineKind::RegionSum => {
+            let regions = get_sudoku_regions(setting, tokenset_index)?;
+            vec![settings::Constraint::EqualSums {
+                tokenset: tokenset_index,
+                regions: split_path(path, regions),
+                values: get_tokenset(setting, tokenset_index)?.symbol_values()?,
+            }]
+        }
+        settings::LineKind::Between => {
+            let (first, interior) = path.cells.split_first().ok_or("path is empty")?;
+            let (last, interior) = interior.split_last().ok_or("path is too short")?;
+            vec![settings::Constraint::Between {
+                tokenset: tokenset_index,
+                ends: (first.clone(), last.clone()),
+                region: shapes::Region {
+                    cells: interior.to_vec(),
+                },
+                order: get_tokenset(setting, tokenset_index)?.symbol_order(),
+            }]
+        }
+        settings::LineKind::Entropic | settings::LineKind::Modular => {
+            let classes = match kind {
+                settings::LineKind::Entropic => {
+                    let order = get_tokenset(setting, tokenset_index)?.symbol_order();
+                    let size = order.len();
+                    if size % 3 != 0 {
+                        return Err("symbol count is not a multiple of 3".into());
+                    }
+                    order
+                        .iter()
+                        .sorted_by_key(|(_, rank)| **rank)
+                        .map(|(symbol, _)| *symbol)
+                        .chunks(size / 3)
+                        .into_iter()
+                        .map(|class| class.collect())
+                        .collect()
+                }
+                _ => {
+                    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+                    (0..3)
+                        .map(|remainder| {
+                            candidates
+                                .chars()
+                                .filter(|symbol| values[symbol] % 3 == remainder)
+                                .collect()
+                        })
+                        .collect::<Vec<_>>()
+                }
+            };
+            path.cells
+                .windows(3)
+                .map(|window| settings::Constraint::DistinctClasses {
+                    tokenset: tokenset_index,
+                    region: shapes::Region {
+                        cells: window.to_vec(),
+                    },
+                    classes: classes.clone(),
+                })
+                .collect()
+        }
+    };
+    Ok(constraints)
+}
+
+fn iter_skyscrapers_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::OutsideClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let order = get_tokenset(setting, tokenset_index)?.symbol_order();
+    clues
+        .iter()
+        .map(|clue| {
+            Ok(settings::Constraint::Visibility {
+                tokenset: tokenset_index,
+                region: outside_region(grid, clue)?,
+                order: order.clone(),
+                count: clue.value,
+            })
+        })
+        .collect()
+}
+
+fn iter_x_sums_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::OutsideClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+    clues
+        .iter()
+        .map(|clue| {
+            Ok(settings::Constraint::XSum {
+                tokenset: tokenset_index,
+                region: outside_region(grid, clue)?,
+                values: values.clone(),
+                sum: clue.value,
+            })
+        })
+        .collect()
+}
+
+fn iter_first_sums_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    count: usize,
+    clues: &[settings::OutsideClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let values = get_tokenset(setting, tokenset_index)?.symbol_values()?;
+    clues
+        .iter()
+        .map(|clue| {
+            Ok(settings::Constraint::RegionSum {
+                tokenset: tokenset_index,
+                region: outside_prefix(grid, clue.side, clue.index, count)?,
+                values: values.clone(),
+                sum: clue.value,
+                distinct: false,
+            })
+        })
+        .collect()
+}
+
+fn iter_outside_symbols_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    count: usize,
+    clues: &[settings::OutsideSymbols],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    clues
+        .iter()
+        .map(|clue| {
+            if clue.symbols.chars().count() > count {
+                return Err("more clue symbols than cells".into());
+            }
+            Ok(settings::Constraint::MustContain {
+                tokenset: tokenset_index,
+                region: outside_prefix(grid, clue.side, clue.index, count)?,
+                symbols: clue.symbols.clone(),
+            })
+        })
+        .collect()
+}
+
+fn iter_quadruple_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    clues: &[settings::QuadrupleClue],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    clues
+        .iter()
+        .map(|clue| {
+            let cells = clue.vertex.cells(grid);
+            if clue.symbols.chars().count() > cells.len() {
+                return Err("more clue symbols than cells around vertex".into());
+            }
+            Ok(settings::Constraint::MustContain {
+                tokenset: tokenset_index,
+                region: shapes::Region { cells },
+                symbols: clue.symbols.clone(),
+            })
+        })
+        .collect()
+}
+
+fn iter_shared_cells_constraints(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+    other_tokenset_index: settings::TokenSetIndex,
+    cells: &[(shapes::Cell, shapes::Cell)],
+) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+    let (grid, _) = get_symbolset(setting, tokenset_index)?;
+    let (other_grid, _) = get_symbolset(setting, other_tokenset_index)?;
+    cells
+        .iter()
+        .map(|(cell, other_cell)| {
+            if !grid.contains(cell) || !other_grid.contains(other_cell) {
+                return Err("shared cell out of grid".into());
+            }
+            Ok(settings::Constraint::SharedCell {
+                tokenset: tokenset_index,
+                cell: cell.clone(),
+                other_tokenset: other_tokenset_index,
+                other_cell: other_cell.clone(),
+            })
+        })
+        .collect()
+}
+
+fn get_sudoku_regions(
+    setting: &settings::PuzzleSetting,
+    tokenset_index: settings::TokenSetIndex,
+) -> Result<&[shapes::Region], Box<dyn std::error::Error>> {
+    setting
+        .rules
+        .iter()
+        .find_map(|rule| match rule {
+            settings::Rule::Sudoku {
+                tokenset, regions, ..
+            } if *tokenset == tokenset_index => Some(&regions[..]),
+            _ => None,
+        })
+        .ok_or_else(|| "no sudoku regions for tokenset".into())
+}
+
+fn split_path(path: &shapes::Path, regions: &[shapes::Region]) -> Vec<shapes::Region> {
+    let mut result: Vec<shapes::Region> = Vec::new();
+    let mut current_region = None;
+    for cell in &path.cells {
+        let region = regions
+            .iter()
+            .position(|region| region.cells.contains(cell));
+        match result.last_mut() {
+            Some(segment) if region == current_region => segment.cells.push(cell.clone()),
+            _ => result.push(shapes::Region {
+                cells: vec![cell.clone()],
+            }),
+        }
+        current_region = region;
+    }
+    result
 }
 
 #[cfg(test)]
@@ -78,12 +900,12 @@ mod tests {
         let grid = setting.add_grid(4, 4);
         let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
         let givens = presets::givens(&[
-            "5...".to_string(),
-            "..6.".to_string(),
+            "4...".to_string(),
+            "..3.".to_string(),
             ".1..".to_string(),
             "....".to_string(),
         ]);
-        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens);
+        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens)?;
 
         let result = list_constraints(&setting)?;
 
@@ -99,14 +921,15 @@ mod tests {
                 region,
             }
         }));
-        expected.extend(shapes::Region::rows(4, 4).map(|region| {
+        let grid = settings::Grid::new(4, 4);
+        expected.extend(shapes::Region::rows(&grid).map(|region| {
             settings::Constraint::SymbolRepartition {
                 tokenset: 0,
                 repartition: repartition.clone(),
                 region,
             }
         }));
-        expected.extend(shapes::Region::columns(4, 4).map(|region| {
+        expected.extend(shapes::Region::columns(&grid).map(|region| {
             settings::Constraint::SymbolRepartition {
                 tokenset: 0,
                 repartition: repartition.clone(),
@@ -118,12 +941,12 @@ mod tests {
                 settings::Constraint::GivenSymbol {
                     tokenset: 0,
                     cell: shapes::Cell(0, 0),
-                    symbol: '5',
+                    symbol: '4',
                 },
                 settings::Constraint::GivenSymbol {
                     tokenset: 0,
                     cell: shapes::Cell(2, 1),
-                    symbol: '6',
+                    symbol: '3',
                 },
                 settings::Constraint::GivenSymbol {
                     tokenset: 0,
@@ -138,4 +961,351 @@ mod tests {
         assert_eq!(result, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_sandwich() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        setting.rules.push(settings::Rule::Sandwich {
+            tokenset,
+            clues: vec![
+                settings::OutsideClue {
+                    side: settings::Side::Right,
+                    index: 1,
+                    value: 5,
+                },
+                settings::OutsideClue {
+                    side: settings::Side::Top,
+                    index: 2,
+                    value: 0,
+                },
+            ],
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let values = settings::digit_values("1234");
+        let expected = [
+            settings::Constraint::Sandwich {
+                tokenset,
+                region: shapes::Region {
+                    cells: (0..4).rev().map(|x| shapes::Cell(x, 1)).collect(),
+                },
+                crusts: ('1', '4'),
+                values: values.clone(),
+                sum: 5,
+            },
+            settings::Constraint::Sandwich {
+                tokenset,
+                region: shapes::Region {
+                    cells: (0..4).map(|y| shapes::Cell(2, y)).collect(),
+                },
+                crusts: ('1', '4'),
+                values,
+                sum: 0,
+            },
+        ];
+        assert_eq!(result[8..], expected[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_little_killer() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        setting.rules.push(settings::Rule::LittleKiller {
+            tokenset,
+            clues: vec![settings::LittleKillerClue {
+                cell: shapes::Cell(1, 0),
+                direction: shapes::Diagonal::DownRight,
+                sum: 7,
+            }],
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = settings::Constraint::RegionSum {
+            tokenset,
+            region: shapes::Region {
+                cells: vec![shapes::Cell(1, 0), shapes::Cell(2, 1), shapes::Cell(3, 2)],
+            },
+            values: settings::digit_values("1234"),
+            sum: 7,
+            distinct: false,
+        };
+        assert_eq!(result[8..], [expected]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_restriction() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(9, 9);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 9, Vec::new(), Vec::new())?;
+        let restrictions = vec![
+            settings::Restriction::Even,
+            settings::Restriction::Odd,
+            settings::Restriction::Low,
+            settings::Restriction::High,
+            settings::Restriction::Symbols {
+                symbols: "378".to_string(),
+            },
+        ];
+        setting.rules.push(settings::Rule::CellRestriction {
+            tokenset,
+            restrictions: restrictions
+                .into_iter()
+                .enumerate()
+                .map(|(index, restriction)| settings::CellRestriction {
+                    cell: shapes::Cell(index, 0),
+                    restriction,
+                })
+                .collect(),
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = ["2468", "13579", "1234", "6789", "378"]
+            .iter()
+            .enumerate()
+            .map(|(index, symbols)| settings::Constraint::AllowedSymbols {
+                tokenset,
+                cell: shapes::Cell(index, 0),
+                symbols: symbols.to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(result[18..], expected[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_inequality_not_adjacent() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        setting.rules.push(settings::Rule::Inequality {
+            tokenset,
+            clues: vec![settings::Inequality {
+                lower: shapes::Cell(0, 0),
+                greater: shapes::Cell(1, 1),
+            }],
+        });
+
+        assert!(list_constraints(&setting).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_outside_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        let cells = vec![shapes::Cell(3, 3), shapes::Cell(4, 4)];
+        setting.rules.push(settings::Rule::Line {
+            tokenset,
+            kind: settings::LineKind::Renban,
+            path: shapes::Path::new(cells)?,
+        });
+
+        assert!(list_constraints(&setting).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_palindrome() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        let cells = vec![
+            shapes::Cell(0, 0),
+            shapes::Cell(1, 1),
+            shapes::Cell(2, 1),
+            shapes::Cell(3, 0),
+            shapes::Cell(3, 1),
+        ];
+        setting.rules.push(settings::Rule::Line {
+            tokenset,
+            kind: settings::LineKind::Palindrome,
+            path: shapes::Path::new(cells)?,
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = [
+            settings::Constraint::SameSymbol {
+                tokenset,
+                first: shapes::Cell(0, 0),
+                second: shapes::Cell(3, 1),
+            },
+            settings::Constraint::SameSymbol {
+                tokenset,
+                first: shapes::Cell(1, 1),
+                second: shapes::Cell(3, 0),
+            },
+        ];
+        assert_eq!(result[8..], expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_region_sum_line() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, regions, Vec::new())?;
+        let cells = vec![
+            shapes::Cell(0, 1),
+            shapes::Cell(1, 1),
+            shapes::Cell(2, 1),
+            shapes::Cell(2, 2),
+            shapes::Cell(3, 3),
+        ];
+        setting.rules.push(settings::Rule::Line {
+            tokenset,
+            kind: settings::LineKind::RegionSum,
+            path: shapes::Path::new(cells)?,
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = settings::Constraint::EqualSums {
+            tokenset,
+            regions: vec![
+                shapes::Region {
+                    cells: vec![shapes::Cell(0, 1), shapes::Cell(1, 1)],
+                },
+                shapes::Region {
+                    cells: vec![shapes::Cell(2, 1)],
+                },
+                shapes::Region {
+                    cells: vec![shapes::Cell(2, 2), shapes::Cell(3, 3)],
+                },
+            ],
+            values: settings::digit_values("1234"),
+        };
+        assert_eq!(result[12..], [expected]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_entropic_order() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(6, 6);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Symbols {
+            grid,
+            candidates: "123456".to_string(),
+            values: settings::SymbolValues::new(),
+            order: "615243"
+                .chars()
+                .enumerate()
+                .map(|(rank, symbol)| (symbol, rank))
+                .collect(),
+        });
+        let cells = vec![shapes::Cell(0, 0), shapes::Cell(1, 0), shapes::Cell(2, 0)];
+        setting.rules.push(settings::Rule::Line {
+            tokenset,
+            kind: settings::LineKind::Entropic,
+            path: shapes::Path::new(cells.clone())?,
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = settings::Constraint::DistinctClasses {
+            tokenset,
+            region: shapes::Region { cells },
+            classes: vec!["61".to_string(), "52".to_string(), "43".to_string()],
+        };
+        assert_eq!(result, [expected]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_outside_prefixes() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        setting.rules.push(settings::Rule::FirstSums {
+            tokenset,
+            count: 2,
+            clues: vec![settings::OutsideClue {
+                side: settings::Side::Bottom,
+                index: 1,
+                value: 5,
+            }],
+        });
+        setting.rules.push(settings::Rule::OutsideSymbols {
+            tokenset,
+            count: 3,
+            clues: vec![settings::OutsideSymbols {
+                side: settings::Side::Right,
+                index: 0,
+                symbols: "14".to_string(),
+            }],
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = [
+            settings::Constraint::RegionSum {
+                tokenset,
+                region: shapes::Region {
+                    cells: vec![shapes::Cell(1, 3), shapes::Cell(1, 2)],
+                },
+                values: settings::digit_values("1234"),
+                sum: 5,
+                distinct: false,
+            },
+            settings::Constraint::MustContain {
+                tokenset,
+                region: shapes::Region {
+                    cells: vec![shapes::Cell(3, 0), shapes::Cell(2, 0), shapes::Cell(1, 0)],
+                },
+                symbols: "14".to_string(),
+            },
+        ];
+        assert_eq!(result[8..], expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quadruple() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        setting.rules.push(settings::Rule::Quadruple {
+            tokenset,
+            clues: vec![settings::QuadrupleClue {
+                vertex: shapes::Vertex(2, 1),
+                symbols: "112".to_string(),
+            }],
+        });
+
+        let result = list_constraints(&setting)?;
+
+        let expected = settings::Constraint::MustContain {
+            tokenset,
+            region: shapes::Region {
+                cells: vec![
+                    shapes::Cell(1, 0),
+                    shapes::Cell(2, 0),
+                    shapes::Cell(1, 1),
+                    shapes::Cell(2, 1),
+                ],
+            },
+            symbols: "112".to_string(),
+        };
+        assert_eq!(result[8..], [expected]);
+
+        setting.rules.push(settings::Rule::Quadruple {
+            tokenset,
+            clues: vec![settings::QuadrupleClue {
+                vertex: shapes::Vertex(4, 0),
+                symbols: "12".to_string(),
+            }],
+        });
+        assert!(list_constraints(&setting).is_err());
+        Ok(())
+    }
 }
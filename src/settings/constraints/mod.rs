@@ -1,6 +1,15 @@
 mod listing;
+mod symbols;
 mod types;
 
 pub use listing::list_constraints;
+pub use symbols::are_consecutive;
+#[cfg(test)]
+pub use symbols::digit_values;
+pub use symbols::sandwich_sum;
+pub use symbols::visible_count;
+pub use symbols::x_sum;
 pub use types::Constraint;
+pub use types::SymbolOrder;
 pub use types::SymbolRepartition;
+pub use types::SymbolValues;
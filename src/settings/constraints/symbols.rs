@@ -0,0 +1,61 @@
+use crate::settings;
+
+pub fn visible_count(symbols: &[settings::SymbolType], order: &settings::SymbolOrder) -> usize {
+    let mut result = 0;
+    let mut highest = None;
+    for rank in symbols.iter().filter_map(|symbol| order.get(symbol)) {
+        if highest.is_none_or(|highest| rank > highest) {
+            result += 1;
+            highest = Some(rank);
+        }
+    }
+    result
+}
+
+pub fn sandwich_sum(
+    symbols: &[settings::SymbolType],
+    crusts: (settings::SymbolType, settings::SymbolType),
+    values: &settings::SymbolValues,
+) -> Option<usize> {
+    let first = symbols.iter().position(|symbol| *symbol == crusts.0)?;
+    let second = symbols.iter().position(|symbol| *symbol == crusts.1)?;
+    let (start, end) = (first.min(second), first.max(second));
+    Some(
+        symbols[start + 1..end]
+            .iter()
+            .map(|symbol| values.get(symbol).unwrap_or(&0))
+            .sum(),
+    )
+}
+
+pub fn x_sum(symbols: &[settings::SymbolType], values: &settings::SymbolValues) -> Option<usize> {
+    let count = *values.get(symbols.first()?)?;
+    if count == 0 || count > symbols.len() {
+        return None;
+    }
+    Some(
+        symbols[..count]
+            .iter()
+            .map(|symbol| values.get(symbol).unwrap_or(&0))
+            .sum(),
+    )
+}
+
+pub fn are_consecutive(
+    order: &settings::SymbolOrder,
+    first: settings::SymbolType,
+    second: settings::SymbolType,
+) -> bool {
+    match (order.get(&first), order.get(&second)) {
+        (Some(first), Some(second)) => first + 1 == *second || second + 1 == *first,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+pub fn digit_values(symbols: &str) -> settings::SymbolValues {
+    symbols
+        .chars()
+        .map(|symbol| (symbol, symbol.to_digit(10).unwrap() as usize))
+        .collect()
+}
@@ -2,6 +2,8 @@ use crate::settings;
 use crate::shapes;
 
 pub type SymbolRepartition = std::collections::HashMap<settings::SymbolType, usize>;
+pub type SymbolValues = std::collections::HashMap<settings::SymbolType, usize>;
+pub type SymbolOrder = std::collections::HashMap<settings::SymbolType, usize>;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum Constraint {
@@ -15,4 +17,135 @@ pub enum Constraint {
         cell: shapes::Cell,
         symbol: settings::SymbolType,
     },
+    GivenShade {
+        tokenset: settings::TokenSetIndex,
+        cell: shapes::Cell,
+        shaded: bool,
+    },
+    GivenEdge {
+        tokenset: settings::TokenSetIndex,
+        edge: shapes::Edge,
+        on: bool,
+    },
+    SingleLoop {
+        tokenset: settings::TokenSetIndex,
+        edges: Vec<shapes::Edge>,
+    },
+    ShadeCount {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        count: usize,
+    },
+    NotBothShaded {
+        tokenset: settings::TokenSetIndex,
+        first: shapes::Cell,
+        second: shapes::Cell,
+    },
+    NotAllShaded {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+    },
+    ShadedConnected {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        adjacency: Vec<(shapes::Cell, shapes::Cell)>,
+    },
+    NumberedIslands {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        adjacency: Vec<(shapes::Cell, shapes::Cell)>,
+        clues: Vec<(shapes::Cell, usize)>,
+    },
+    Sandwich {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        crusts: (settings::SymbolType, settings::SymbolType),
+        values: SymbolValues,
+        sum: usize,
+    },
+    RegionSum {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        values: SymbolValues,
+        sum: usize,
+        distinct: bool,
+    },
+    Different {
+        tokenset: settings::TokenSetIndex,
+        first: shapes::Cell,
+        second: shapes::Cell,
+    },
+    NonConsecutive {
+        tokenset: settings::TokenSetIndex,
+        first: shapes::Cell,
+        second: shapes::Cell,
+        order: SymbolOrder,
+    },
+    AllowedSymbols {
+        tokenset: settings::TokenSetIndex,
+        cell: shapes::Cell,
+        symbols: String,
+    },
+    Less {
+        tokenset: settings::TokenSetIndex,
+        lower: shapes::Cell,
+        greater: shapes::Cell,
+        order: SymbolOrder,
+    },
+    Renban {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        order: SymbolOrder,
+    },
+    MinDifference {
+        tokenset: settings::TokenSetIndex,
+        first: shapes::Cell,
+        second: shapes::Cell,
+        values: SymbolValues,
+        difference: usize,
+    },
+    SameSymbol {
+        tokenset: settings::TokenSetIndex,
+        first: shapes::Cell,
+        second: shapes::Cell,
+    },
+    EqualSums {
+        tokenset: settings::TokenSetIndex,
+        regions: Vec<shapes::Region>,
+        values: SymbolValues,
+    },
+    Between {
+        tokenset: settings::TokenSetIndex,
+        ends: (shapes::Cell, shapes::Cell),
+        region: shapes::Region,
+        order: SymbolOrder,
+    },
+    DistinctClasses {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        classes: Vec<String>,
+    },
+    Visibility {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        order: SymbolOrder,
+        count: usize,
+    },
+    XSum {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        values: SymbolValues,
+        sum: usize,
+    },
+    MustContain {
+        tokenset: settings::TokenSetIndex,
+        region: shapes::Region,
+        symbols: String,
+    },
+    SharedCell {
+        tokenset: settings::TokenSetIndex,
+        cell: shapes::Cell,
+        other_tokenset: settings::TokenSetIndex,
+        other_cell: shapes::Cell,
+    },
 }
@@ -1,5 +1,65 @@
+use crate::shapes;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topology {
+    #[default]
+    Plane,
+    Torus,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct Grid {
     pub rows: usize,
     pub columns: usize,
+    #[serde(default)]
+    pub missing: std::collections::BTreeSet<shapes::Cell>,
+    #[serde(default)]
+    pub topology: Topology,
+    #[serde(default)]
+    pub geometry: shapes::Geometry,
+}
+
+impl Grid {
+    pub fn new(rows: usize, columns: usize) -> Grid {
+        Grid {
+            rows,
+            columns,
+            missing: std::collections::BTreeSet::new(),
+            topology: Topology::Plane,
+            geometry: shapes::Geometry::Square,
+        }
+    }
+
+    pub fn hexagon(radius: usize) -> Grid {
+        let size = 2 * radius + 1;
+        let missing = (0..size)
+            .flat_map(|y| (0..size).map(move |x| shapes::Cell(x, y)))
+            .filter(|cell| cell.0 + cell.1 < radius || cell.0 + cell.1 > 3 * radius)
+            .collect();
+        Grid {
+            missing,
+            geometry: shapes::Geometry::Hex,
+            ..Grid::new(size, size)
+        }
+    }
+
+    pub fn triangle(size: usize) -> Grid {
+        // The apex column must hold an up-pointing cell on the first row.
+        let apex = size - 1 + (size + 1) % 2;
+        let columns = apex + size;
+        let missing = (0..size)
+            .flat_map(|y| (0..columns).map(move |x| shapes::Cell(x, y)))
+            .filter(|cell| cell.0 + cell.1 < apex || cell.0 > apex + cell.1)
+            .collect();
+        Grid {
+            missing,
+            geometry: shapes::Geometry::Triangle,
+            ..Grid::new(size, columns)
+        }
+    }
+
+    pub fn contains(&self, cell: &shapes::Cell) -> bool {
+        cell.0 < self.columns && cell.1 < self.rows && !self.missing.contains(cell)
+    }
 }
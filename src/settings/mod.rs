@@ -6,13 +6,35 @@ mod rules;
 mod tokens;
 mod types;
 
+pub use constraints::are_consecutive;
+#[cfg(test)]
+pub use constraints::digit_values;
 pub use constraints::list_constraints;
+pub use constraints::sandwich_sum;
+pub use constraints::visible_count;
+pub use constraints::x_sum;
 pub use constraints::Constraint;
+pub use constraints::SymbolOrder;
 pub use constraints::SymbolRepartition;
+pub use constraints::SymbolValues;
 pub use grids::Grid;
+pub use grids::Topology;
 pub use puzzle_settings::PuzzleSetting;
+pub use rules::CellRestriction;
+pub use rules::GivenEdge;
+pub use rules::GivenShade;
 pub use rules::GivenSymbol;
+pub use rules::Inequality;
+pub use rules::IslandClue;
+pub use rules::KakuroClue;
+pub use rules::LineKind;
+pub use rules::LittleKillerClue;
+pub use rules::OutsideClue;
+pub use rules::OutsideSymbols;
+pub use rules::QuadrupleClue;
+pub use rules::Restriction;
 pub use rules::Rule;
+pub use rules::Side;
 pub use tokens::TokenSet;
 pub use types::GridIndex;
 pub use types::SymbolType;
@@ -1,6 +1,19 @@
 use crate::settings;
 use crate::shapes;
 
+const SYMBOLS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn alphabet(size: usize) -> Result<String, Box<dyn std::error::Error>> {
+    if size > SYMBOLS.len() {
+        return Err(format!("no default alphabet for {} symbols", size).into());
+    }
+    Ok(if size <= 9 {
+        SYMBOLS.chars().skip(1).take(size).collect()
+    } else {
+        SYMBOLS.chars().take(size).collect()
+    })
+}
+
 pub fn givens(string_grid: &[String]) -> Vec<settings::GivenSymbol> {
     string_grid
         .iter()
@@ -19,3 +32,72 @@ pub fn givens(string_grid: &[String]) -> Vec<settings::GivenSymbol> {
         })
         .collect::<Vec<_>>()
 }
+
+pub fn regions(layout: &[String]) -> Result<Vec<shapes::Region>, Box<dyn std::error::Error>> {
+    let mut names = Vec::new();
+    let mut result: Vec<shapes::Region> = Vec::new();
+    for (row, row_contents) in layout.iter().enumerate() {
+        for (column, name) in row_contents.chars().enumerate() {
+            if let '.' | ' ' = name {
+                continue;
+            }
+            let cell = shapes::Cell(column, row);
+            match names.iter().position(|other| *other == name) {
+                Some(index) => result[index].cells.push(cell),
+                None => {
+                    names.push(name);
+                    result.push(shapes::Region { cells: vec![cell] });
+                }
+            }
+        }
+    }
+    let columns = layout.iter().map(|row| row.chars().count()).max();
+    let grid = settings::Grid::new(layout.len(), columns.unwrap_or(0));
+    for (name, region) in names.iter().zip(&result) {
+        if !region.is_connected(&grid) {
+            return Err(format!("region {} is not connected", name).into());
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alphabet() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(alphabet(4)?, "1234");
+        assert_eq!(alphabet(9)?, "123456789");
+        assert_eq!(alphabet(16)?, "0123456789ABCDEF");
+        assert_eq!(alphabet(25)?.chars().last(), Some('O'));
+        assert_eq!(alphabet(36)?.len(), 36);
+        assert!(alphabet(37).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_regions() -> Result<(), Box<dyn std::error::Error>> {
+        let layout = ["AAB".to_string(), "ABB".to_string(), "CCC".to_string()];
+        let result = regions(&layout)?;
+        let expected = vec![
+            shapes::Region {
+                cells: vec![shapes::Cell(0, 0), shapes::Cell(1, 0), shapes::Cell(0, 1)],
+            },
+            shapes::Region {
+                cells: vec![shapes::Cell(2, 0), shapes::Cell(1, 1), shapes::Cell(2, 1)],
+            },
+            shapes::Region {
+                cells: vec![shapes::Cell(0, 2), shapes::Cell(1, 2), shapes::Cell(2, 2)],
+            },
+        ];
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disconnected_region() {
+        let layout = ["ABA".to_string(), "BBB".to_string(), "CCC".to_string()];
+        assert!(regions(&layout).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::settings;
+use crate::settings::presets;
+use crate::settings::presets::sudoku;
+
+pub fn setting(
+    givens: &[String],
+    inequalities: Vec<settings::Inequality>,
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = givens.len();
+    let mut setting = settings::PuzzleSetting::default();
+    let grid = setting.add_grid(size, size);
+    let givens = presets::givens(givens);
+    let tokenset = sudoku::add_symbolset(&mut setting, grid, size, Vec::new(), givens)?;
+    setting.rules.push(settings::Rule::Inequality {
+        tokenset,
+        clues: inequalities,
+    });
+    Ok(setting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes;
+    use crate::solving;
+    use crate::states;
+
+    #[test]
+    fn test_unique_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = vec!["...".to_string(), "...".to_string(), "...".to_string()];
+        let inequalities = vec![
+            settings::Inequality {
+                lower: shapes::Cell(0, 0),
+                greater: shapes::Cell(1, 0),
+            },
+            settings::Inequality {
+                lower: shapes::Cell(1, 0),
+                greater: shapes::Cell(2, 0),
+            },
+            settings::Inequality {
+                lower: shapes::Cell(0, 2),
+                greater: shapes::Cell(0, 1),
+            },
+        ];
+        let setting = setting(&grid, inequalities)?;
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(&grid, states::CellState::Empty)],
+        };
+        let result = solving::count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 1);
+        Ok(())
+    }
+}
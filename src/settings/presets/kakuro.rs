@@ -0,0 +1,87 @@
+use crate::settings;
+use crate::settings::presets;
+use crate::shapes;
+
+pub fn setting(layout: &[String]) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let rows = layout
+        .iter()
+        .map(|row| row.split_whitespace().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let columns = rows.first().map(|row| row.len()).unwrap_or(0);
+    if rows.iter().any(|row| row.len() != columns) {
+        return Err("kakuro rows have different lengths".into());
+    }
+    let mut missing = std::collections::BTreeSet::new();
+    let mut clues = Vec::new();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, token) in row.iter().enumerate() {
+            let cell = shapes::Cell(x, y);
+            match *token {
+                "." => continue,
+                "#" => (),
+                _ => {
+                    let (down, across) = token
+                        .split_once('\\')
+                        .ok_or_else(|| format!("invalid kakuro cell {}", token))?;
+                    clues.push(settings::KakuroClue {
+                        cell: cell.clone(),
+                        across: parse_sum(across)?,
+                        down: parse_sum(down)?,
+                    });
+                }
+            }
+            missing.insert(cell);
+        }
+    }
+    let mut setting = settings::PuzzleSetting::default();
+    let grid = setting.add_masked_grid(rows.len(), columns, missing);
+    let tokenset = setting.add_tokenset(settings::TokenSet::Symbols {
+        grid,
+        candidates: presets::alphabet(9)?,
+        values: settings::SymbolValues::new(),
+        order: settings::SymbolOrder::new(),
+    });
+    setting
+        .rules
+        .push(settings::Rule::Kakuro { tokenset, clues });
+    Ok(setting)
+}
+
+fn parse_sum(text: &str) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+    Ok(if text.is_empty() {
+        None
+    } else {
+        Some(text.parse()?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving;
+    use crate::states;
+
+    #[test]
+    fn test_unique_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let layout = vec![
+            r"#  4\ 3\".to_string(),
+            r"\3 .  .".to_string(),
+            r"\4 .  .".to_string(),
+        ];
+        let setting = setting(&layout)?;
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(constraints.len(), 4);
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        let result = solving::count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_layout() {
+        let layout = vec![r"#  4\".to_string(), r"\3 . .".to_string()];
+        assert!(setting(&layout).is_err());
+        let layout = vec![r"# 4/".to_string(), r"\3 .".to_string()];
+        assert!(setting(&layout).is_err());
+    }
+}
@@ -1,4 +1,10 @@
 mod common;
+pub mod futoshiki;
+pub mod kakuro;
+pub mod skyscrapers;
+pub mod star_battle;
 pub mod sudoku;
 
+pub use common::alphabet;
 pub use common::givens;
+pub use common::regions;
@@ -0,0 +1,49 @@
+use crate::settings;
+use crate::settings::presets;
+use crate::settings::presets::sudoku;
+
+pub fn setting(
+    givens: &[String],
+    clues: Vec<settings::OutsideClue>,
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = givens.len();
+    let mut setting = settings::PuzzleSetting::default();
+    let grid = setting.add_grid(size, size);
+    let givens = presets::givens(givens);
+    let tokenset = sudoku::add_symbolset(&mut setting, grid, size, Vec::new(), givens)?;
+    setting
+        .rules
+        .push(settings::Rule::Skyscrapers { tokenset, clues });
+    Ok(setting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving;
+    use crate::states;
+
+    #[test]
+    fn test_unique_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = vec!["...".to_string(), "...".to_string(), "...".to_string()];
+        let clues = vec![
+            settings::OutsideClue {
+                side: settings::Side::Left,
+                index: 0,
+                value: 3,
+            },
+            settings::OutsideClue {
+                side: settings::Side::Top,
+                index: 0,
+                value: 3,
+            },
+        ];
+        let setting = setting(&grid, clues)?;
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(&grid, states::CellState::Empty)],
+        };
+        let result = solving::count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::settings;
+use crate::settings::presets;
+
+pub fn setting(
+    layout: &[String],
+    stars_per_unit: usize,
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = layout.len();
+    if layout.iter().any(|row| row.chars().count() != size) {
+        return Err("layout is not square".into());
+    }
+    let regions = presets::regions(layout)?;
+    let mut setting = settings::PuzzleSetting::default();
+    let grid = setting.add_grid(size, size);
+    let tokenset = setting.add_tokenset(settings::TokenSet::Shading { grid });
+    setting.rules.push(settings::Rule::StarBattle {
+        tokenset,
+        stars_per_unit,
+        regions,
+    });
+    Ok(setting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving;
+    use crate::states;
+
+    #[test]
+    fn test_unique_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let layout = vec![
+            "DBBEE".to_string(),
+            "DDBEE".to_string(),
+            "DDAAE".to_string(),
+            "CAAAE".to_string(),
+            "CAAAA".to_string(),
+        ];
+        let setting = setting(&layout, 1)?;
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        let result = solving::count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 1);
+
+        let constraints = settings::list_constraints(&setting)?;
+        let solution = vec![
+            "oo#oo".to_string(),
+            "oooo#".to_string(),
+            "o#ooo".to_string(),
+            "ooo#o".to_string(),
+            "#oooo".to_string(),
+        ];
+        let state = states::State {
+            tokensets: vec![states::shadeset(&solution)],
+        };
+        assert_eq!(
+            states::compute_status(&state, &constraints)?,
+            states::Status::Valid
+        );
+        Ok(())
+    }
+}
@@ -2,17 +2,168 @@ use crate::settings;
 use crate::settings::presets;
 use crate::shapes;
 
-pub fn setting(box_width: usize, givens: &[String]) -> settings::PuzzleSetting {
+pub fn setting(
+    box_width: usize,
+    givens: &[String],
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    setting_with_extra_regions(box_width, givens, Vec::new())
+}
+
+pub fn x_setting(
+    box_width: usize,
+    givens: &[String],
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = givens.len();
+    let diagonals = vec![
+        shapes::Region::main_diagonal(size),
+        shapes::Region::anti_diagonal(size),
+    ];
+    setting_with_extra_regions(box_width, givens, diagonals)
+}
+
+pub fn setting_with_extra_regions(
+    box_width: usize,
+    givens: &[String],
+    extra_regions: Vec<shapes::Region>,
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
     // TODO: sizes checks
     let size = givens.len();
     let box_height = size / box_width;
     let mut setting = settings::PuzzleSetting::default();
     let grid = setting.add_grid(size, size);
-    let regions =
-        shapes::Region::grid_boxes(box_width, box_height, box_width, box_height).collect();
+    let regions = shapes::Region::grid_boxes(box_width, box_height, box_width, box_height)
+        .chain(extra_regions)
+        .collect();
     let givens = presets::givens(givens);
-    add_symbolset(&mut setting, grid, size, regions, givens);
-    setting
+    add_symbolset(&mut setting, grid, size, regions, givens)?;
+    Ok(setting)
+}
+
+pub fn jigsaw_setting(
+    layout: &[String],
+    givens: &[String],
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = givens.len();
+    if layout.len() != size || layout.iter().any(|row| row.chars().count() != size) {
+        return Err("layout does not match the grid size".into());
+    }
+    if layout.iter().any(|row| row.contains(['.', ' '])) {
+        return Err("layout has cells outside of any region".into());
+    }
+    let regions = presets::regions(layout)?;
+    if regions.len() != size {
+        return Err("region count does not match the symbol count".into());
+    }
+    if regions.iter().any(|region| region.cells.len() != size) {
+        return Err("region size does not match the symbol count".into());
+    }
+    let mut setting = settings::PuzzleSetting::default();
+    let grid = setting.add_grid(size, size);
+    let givens = presets::givens(givens);
+    add_symbolset(&mut setting, grid, size, regions, givens)?;
+    Ok(setting)
+}
+
+pub fn samurai_setting(
+    box_width: usize,
+    givens: &[String],
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = box_width * box_width;
+    let offset = size - box_width;
+    let origins = [
+        shapes::Cell(0, 0),
+        shapes::Cell(2 * offset, 0),
+        shapes::Cell(offset, offset),
+        shapes::Cell(0, 2 * offset),
+        shapes::Cell(2 * offset, 2 * offset),
+    ];
+    overlapping_setting(box_width, &origins, givens)
+}
+
+pub fn overlapping_setting(
+    box_width: usize,
+    origins: &[shapes::Cell],
+    givens: &[String],
+) -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
+    let size = box_width * box_width;
+    let mut setting = settings::PuzzleSetting::default();
+    let tokensets = origins
+        .iter()
+        .map(|origin| {
+            let grid = setting.add_grid(size, size);
+            let regions =
+                shapes::Region::grid_boxes(box_width, box_width, box_width, box_width).collect();
+            let sub_givens = givens
+                .iter()
+                .skip(origin.1)
+                .take(size)
+                .map(|row| row.chars().skip(origin.0).take(size).collect())
+                .collect::<Vec<String>>();
+            let sub_givens = presets::givens(&sub_givens);
+            add_symbolset(&mut setting, grid, size, regions, sub_givens)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for (index, origin) in origins.iter().enumerate() {
+        for other_index in index + 1..origins.len() {
+            let other_origin = &origins[other_index];
+            let cells = (0..size)
+                .flat_map(|row| (0..size).map(move |column| (column, row)))
+                .filter_map(|(column, row)| {
+                    let (x, y) = (origin.0 + column, origin.1 + row);
+                    if x < other_origin.0
+                        || y < other_origin.1
+                        || x >= other_origin.0 + size
+                        || y >= other_origin.1 + size
+                    {
+                        return None;
+                    }
+                    Some((
+                        shapes::Cell(column, row),
+                        shapes::Cell(x - other_origin.0, y - other_origin.1),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            if !cells.is_empty() {
+                setting.rules.push(settings::Rule::SharedCells {
+                    tokenset: tokensets[index],
+                    other_tokenset: tokensets[other_index],
+                    cells,
+                });
+            }
+        }
+    }
+    Ok(setting)
+}
+
+pub fn disjoint_groups(box_width: usize, box_height: usize) -> Vec<shapes::Region> {
+    let boxes = shapes::Region::grid_boxes(box_height, box_width, box_width, box_height)
+        .collect::<Vec<_>>();
+    (0..box_width * box_height)
+        .map(|position| shapes::Region {
+            cells: boxes
+                .iter()
+                .map(|region| region.cells[position].clone())
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn windoku(box_size: usize) -> Vec<shapes::Region> {
+    let count = box_size - 1;
+    shapes::Region::grid_boxes(count, count, box_size, box_size)
+        .map(|region| shapes::Region {
+            cells: region
+                .cells
+                .into_iter()
+                .map(|cell| {
+                    shapes::Cell(
+                        cell.0 + 1 + cell.0 / box_size,
+                        cell.1 + 1 + cell.1 / box_size,
+                    )
+                })
+                .collect(),
+        })
+        .collect()
 }
 
 pub fn add_symbolset(
@@ -21,13 +172,27 @@ pub fn add_symbolset(
     digits: usize,
     regions: Vec<shapes::Region>,
     givens: Vec<settings::GivenSymbol>,
+) -> Result<settings::TokenSetIndex, Box<dyn std::error::Error>> {
+    let candidates = presets::alphabet(digits)?;
+    Ok(add_custom_symbolset(
+        setting, grid, candidates, regions, givens,
+    ))
+}
+
+pub fn add_custom_symbolset(
+    setting: &mut settings::PuzzleSetting,
+    grid: settings::GridIndex,
+    candidates: String,
+    regions: Vec<shapes::Region>,
+    givens: Vec<settings::GivenSymbol>,
 ) -> settings::TokenSetIndex {
-    let candidates = (1..=digits)
-        .map(|digit| digit.to_string())
-        .collect::<Vec<_>>()
-        .join("");
-    let category = settings::TokenSet::Symbols { candidates, grid };
-    let tokenset = setting.add_symbolset(category);
+    let category = settings::TokenSet::Symbols {
+        candidates,
+        grid,
+        values: settings::SymbolValues::new(),
+        order: settings::SymbolOrder::new(),
+    };
+    let tokenset = setting.add_tokenset(category);
     let rule = settings::Rule::Sudoku {
         tokenset,
         regions,
@@ -36,3 +201,163 @@ pub fn add_symbolset(
     setting.rules.push(rule);
     tokenset
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving;
+    use crate::states;
+
+    #[test]
+    fn test_x_setting() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = vec![
+            "1...".to_string(),
+            "....".to_string(),
+            "....".to_string(),
+            "...3".to_string(),
+        ];
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(&grid, states::CellState::Empty)],
+        };
+        let regular = solving::count_solutions(&setting(2, &grid)?, &mut state)?;
+        let diagonal = solving::count_solutions(&x_setting(2, &grid)?, &mut state)?;
+        assert_eq!(regular, 18);
+        assert_eq!(diagonal, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disjoint_groups() {
+        let mut result = disjoint_groups(2, 2);
+        result.iter_mut().for_each(|region| region.cells.sort());
+        let expected = vec![
+            shapes::Region {
+                cells: vec![
+                    shapes::Cell(0, 0),
+                    shapes::Cell(0, 2),
+                    shapes::Cell(2, 0),
+                    shapes::Cell(2, 2),
+                ],
+            },
+            shapes::Region {
+                cells: vec![
+                    shapes::Cell(0, 1),
+                    shapes::Cell(0, 3),
+                    shapes::Cell(2, 1),
+                    shapes::Cell(2, 3),
+                ],
+            },
+            shapes::Region {
+                cells: vec![
+                    shapes::Cell(1, 0),
+                    shapes::Cell(1, 2),
+                    shapes::Cell(3, 0),
+                    shapes::Cell(3, 2),
+                ],
+            },
+            shapes::Region {
+                cells: vec![
+                    shapes::Cell(1, 1),
+                    shapes::Cell(1, 3),
+                    shapes::Cell(3, 1),
+                    shapes::Cell(3, 3),
+                ],
+            },
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_windoku() {
+        let mut result = windoku(3);
+        result.iter_mut().for_each(|region| region.cells.sort());
+        result.sort();
+        let window = |x: usize, y: usize| shapes::Region {
+            cells: (x..x + 3)
+                .flat_map(|column| (y..y + 3).map(move |row| shapes::Cell(column, row)))
+                .collect(),
+        };
+        let expected = vec![window(1, 1), window(1, 5), window(5, 1), window(5, 5)];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_jigsaw_setting() -> Result<(), Box<dyn std::error::Error>> {
+        let layout = vec![
+            "AAAB".to_string(),
+            "ACBB".to_string(),
+            "CCDB".to_string(),
+            "CDDD".to_string(),
+        ];
+        let grid = vec![
+            "1...".to_string(),
+            "....".to_string(),
+            "....".to_string(),
+            "....".to_string(),
+        ];
+        let setting = jigsaw_setting(&layout, &grid)?;
+        match &setting.rules[0] {
+            settings::Rule::Sudoku { regions, .. } => assert_eq!(regions.len(), 4),
+            rule => panic!("unexpected rule {:?}", rule),
+        }
+
+        let layout = vec![
+            "AAAB".to_string(),
+            "ABBB".to_string(),
+            "CCCC".to_string(),
+            "DDDC".to_string(),
+        ];
+        assert!(jigsaw_setting(&layout, &grid).is_err());
+
+        let layout = vec![
+            "AAAB".to_string(),
+            "ABBB".to_string(),
+            "CCCC".to_string(),
+            "....".to_string(),
+        ];
+        assert!(jigsaw_setting(&layout, &grid).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_samurai_setting() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = vec![
+            ".1.3....".to_string(),
+            "2....4.1".to_string(),
+            "........".to_string(),
+            "...1....".to_string(),
+            ".......3".to_string(),
+            "1.......".to_string(),
+            "3..1....".to_string(),
+            "......32".to_string(),
+        ];
+        let setting = samurai_setting(2, &grid)?;
+        assert_eq!(setting.tokensets.len(), 5);
+        let shared = setting
+            .rules
+            .iter()
+            .filter_map(|rule| match rule {
+                settings::Rule::SharedCells { cells, .. } => Some(cells.len()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(shared, [4, 4, 4, 4]);
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        assert_eq!(solving::count_solutions(&setting, &mut state)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_setting() -> Result<(), Box<dyn std::error::Error>> {
+        let mut grid = vec![".".repeat(16); 16];
+        grid[0] = "0123456789ABCDEF".to_string();
+        let large = setting(4, &grid)?;
+        let constraints = settings::list_constraints(&large)?;
+        assert_eq!(constraints.len(), 3 * 16 + 16);
+
+        grid[1] = "G...............".to_string();
+        let invalid = setting(4, &grid)?;
+        assert!(settings::list_constraints(&invalid).is_err());
+        Ok(())
+    }
+}
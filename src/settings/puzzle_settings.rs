@@ -1,6 +1,7 @@
 use crate::settings;
+use crate::shapes;
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct PuzzleSetting {
     pub grids: Vec<settings::Grid>,
     pub tokensets: Vec<settings::TokenSet>,
@@ -10,22 +11,25 @@ pub struct PuzzleSetting {
 impl PuzzleSetting {
     pub fn add_grid(&mut self, rows: usize, columns: usize) -> settings::GridIndex {
         let index = self.grids.len() as settings::GridIndex;
-        self.grids.push(settings::Grid { rows, columns });
+        self.grids.push(settings::Grid::new(rows, columns));
         index
     }
-    pub fn add_symbolset(&mut self, tokenset: settings::TokenSet) -> settings::TokenSetIndex {
+    pub fn add_masked_grid(
+        &mut self,
+        rows: usize,
+        columns: usize,
+        missing: std::collections::BTreeSet<shapes::Cell>,
+    ) -> settings::GridIndex {
+        let index = self.grids.len() as settings::GridIndex;
+        self.grids.push(settings::Grid {
+            missing,
+            ..settings::Grid::new(rows, columns)
+        });
+        index
+    }
+    pub fn add_tokenset(&mut self, tokenset: settings::TokenSet) -> settings::TokenSetIndex {
         let index = self.tokensets.len() as settings::TokenSetIndex;
         self.tokensets.push(tokenset);
         index
     }
 }
-
-impl Default for PuzzleSetting {
-    fn default() -> PuzzleSetting {
-        PuzzleSetting {
-            grids: Vec::new(),
-            tokensets: Vec::new(),
-            rules: Vec::new(),
-        }
-    }
-}
@@ -7,6 +7,102 @@ pub struct GivenSymbol {
     pub cell: shapes::Cell,
 }
 
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct GivenShade {
+    pub shaded: bool,
+    pub cell: shapes::Cell,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct IslandClue {
+    pub size: usize,
+    pub cell: shapes::Cell,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct GivenEdge {
+    pub on: bool,
+    pub edge: shapes::Edge,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct OutsideClue {
+    pub side: Side,
+    pub index: usize,
+    pub value: usize,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct OutsideSymbols {
+    pub side: Side,
+    pub index: usize,
+    pub symbols: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct QuadrupleClue {
+    pub vertex: shapes::Vertex,
+    pub symbols: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct LittleKillerClue {
+    pub cell: shapes::Cell,
+    pub direction: shapes::Diagonal,
+    pub sum: usize,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct KakuroClue {
+    pub cell: shapes::Cell,
+    pub across: Option<usize>,
+    pub down: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Restriction {
+    Even,
+    Odd,
+    Low,
+    High,
+    Symbols { symbols: String },
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct CellRestriction {
+    pub cell: shapes::Cell,
+    pub restriction: Restriction,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Inequality {
+    pub lower: shapes::Cell,
+    pub greater: shapes::Cell,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    Renban,
+    GermanWhispers,
+    DutchWhispers,
+    Palindrome,
+    RegionSum,
+    Between,
+    Entropic,
+    Modular,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Rule {
@@ -15,4 +111,83 @@ pub enum Rule {
         regions: Vec<shapes::Region>,
         givens: Vec<GivenSymbol>,
     },
+    Sandwich {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<OutsideClue>,
+    },
+    LittleKiller {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<LittleKillerClue>,
+    },
+    AntiChess {
+        tokenset: settings::TokenSetIndex,
+        moves: shapes::Moves,
+    },
+    NonConsecutive {
+        tokenset: settings::TokenSetIndex,
+    },
+    CellRestriction {
+        tokenset: settings::TokenSetIndex,
+        restrictions: Vec<CellRestriction>,
+    },
+    Inequality {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<Inequality>,
+    },
+    Line {
+        tokenset: settings::TokenSetIndex,
+        kind: LineKind,
+        path: shapes::Path,
+    },
+    Skyscrapers {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<OutsideClue>,
+    },
+    XSums {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<OutsideClue>,
+    },
+    FirstSums {
+        tokenset: settings::TokenSetIndex,
+        count: usize,
+        clues: Vec<OutsideClue>,
+    },
+    OutsideSymbols {
+        tokenset: settings::TokenSetIndex,
+        count: usize,
+        clues: Vec<OutsideSymbols>,
+    },
+    Quadruple {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<QuadrupleClue>,
+    },
+    Shading {
+        tokenset: settings::TokenSetIndex,
+        givens: Vec<GivenShade>,
+    },
+    Edges {
+        tokenset: settings::TokenSetIndex,
+        givens: Vec<GivenEdge>,
+    },
+    SingleLoop {
+        tokenset: settings::TokenSetIndex,
+    },
+    StarBattle {
+        tokenset: settings::TokenSetIndex,
+        stars_per_unit: usize,
+        regions: Vec<shapes::Region>,
+    },
+    Nurikabe {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<IslandClue>,
+    },
+    Kakuro {
+        tokenset: settings::TokenSetIndex,
+        clues: Vec<KakuroClue>,
+    },
+    SharedCells {
+        tokenset: settings::TokenSetIndex,
+        other_tokenset: settings::TokenSetIndex,
+        cells: Vec<(shapes::Cell, shapes::Cell)>,
+    },
 }
@@ -1,4 +1,6 @@
 use super::types;
+use crate::settings;
+use crate::shapes;
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -6,5 +8,111 @@ pub enum TokenSet {
     Symbols {
         grid: types::GridIndex,
         candidates: String,
+        #[serde(default)]
+        values: settings::SymbolValues,
+        #[serde(default)]
+        order: settings::SymbolOrder,
     },
+    Shading {
+        grid: types::GridIndex,
+    },
+    Edges {
+        grid: types::GridIndex,
+        lattice: shapes::Lattice,
+    },
+}
+
+impl TokenSet {
+    pub fn symbol_order(&self) -> settings::SymbolOrder {
+        match self {
+            TokenSet::Symbols {
+                candidates, order, ..
+            } if order.is_empty() => candidates
+                .chars()
+                .enumerate()
+                .map(|(rank, symbol)| (symbol, rank))
+                .collect(),
+            TokenSet::Symbols {
+                candidates, order, ..
+            } => candidates
+                .chars()
+                .filter_map(|symbol| order.get(&symbol).map(|rank| (symbol, *rank)))
+                .collect(),
+            _ => settings::SymbolOrder::new(),
+        }
+    }
+
+    pub fn symbol_values(&self) -> Result<settings::SymbolValues, Box<dyn std::error::Error>> {
+        match self {
+            TokenSet::Symbols {
+                candidates, values, ..
+            } => candidates
+                .chars()
+                .map(|symbol| {
+                    let value = values
+                        .get(&symbol)
+                        .copied()
+                        .or_else(|| symbol.to_digit(36).map(|value| value as usize));
+                    match value {
+                        Some(value) => Ok((symbol, value)),
+                        None => Err(format!("symbol {} has no numeric value", symbol).into()),
+                    }
+                })
+                .collect(),
+            _ => Err("tokenset has no symbol values".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_values() -> Result<(), Box<dyn std::error::Error>> {
+        let tokenset = TokenSet::Symbols {
+            grid: 0,
+            candidates: "09AX".to_string(),
+            values: [('X', 100)].iter().cloned().collect(),
+            order: settings::SymbolOrder::new(),
+        };
+        let expected = [('0', 0), ('9', 9), ('A', 10), ('X', 100)]
+            .iter()
+            .cloned()
+            .collect::<settings::SymbolValues>();
+        assert_eq!(tokenset.symbol_values()?, expected);
+
+        let tokenset = TokenSet::Symbols {
+            grid: 0,
+            candidates: "12*".to_string(),
+            values: settings::SymbolValues::new(),
+            order: settings::SymbolOrder::new(),
+        };
+        assert!(tokenset.symbol_values().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_symbol_order() {
+        let tokenset = TokenSet::Symbols {
+            grid: 0,
+            candidates: "ABC".to_string(),
+            values: settings::SymbolValues::new(),
+            order: settings::SymbolOrder::new(),
+        };
+        let expected = [('A', 0), ('B', 1), ('C', 2)].iter().cloned().collect();
+        assert_eq!(tokenset.symbol_order(), expected);
+
+        let tokenset = TokenSet::Symbols {
+            grid: 0,
+            candidates: "ABC".to_string(),
+            values: settings::SymbolValues::new(),
+            order: [('A', 2), ('B', 0), ('C', 1), ('D', 3)]
+                .iter()
+                .cloned()
+                .collect(),
+        };
+        let expected = [('A', 2), ('B', 0), ('C', 1)].iter().cloned().collect();
+        assert_eq!(tokenset.symbol_order(), expected);
+    }
 }
@@ -1,4 +1,26 @@
+use crate::settings;
+
 #[derive(
     Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
 )]
 pub struct Cell(pub usize, pub usize);
+
+impl Cell {
+    pub fn offset(&self, dx: isize, dy: isize, grid: &settings::Grid) -> Option<Cell> {
+        let mut x = self.0 as isize + dx;
+        let mut y = self.1 as isize + dy;
+        if let settings::Topology::Torus = grid.topology {
+            x = x.rem_euclid(grid.columns as isize);
+            y = y.rem_euclid(grid.rows as isize);
+        }
+        if x < 0 || y < 0 {
+            return None;
+        }
+        let cell = Cell(x as usize, y as usize);
+        if grid.contains(&cell) {
+            Some(cell)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,19 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Diagonal {
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Diagonal {
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Diagonal::UpLeft => (-1, -1),
+            Diagonal::UpRight => (1, -1),
+            Diagonal::DownLeft => (-1, 1),
+            Diagonal::DownRight => (1, 1),
+        }
+    }
+}
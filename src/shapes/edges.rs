@@ -0,0 +1,88 @@
+use super::cells;
+use crate::settings;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lattice {
+    Centers,
+    Borders,
+}
+
+impl Lattice {
+    pub fn size(self, grid: &settings::Grid) -> (usize, usize) {
+        match self {
+            Lattice::Centers => (grid.columns, grid.rows),
+            Lattice::Borders => (grid.columns + 1, grid.rows + 1),
+        }
+    }
+}
+
+#[derive(
+    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Edge {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+impl Edge {
+    pub fn ends(&self) -> [(usize, usize); 2] {
+        match *self {
+            Edge::Horizontal(x, y) => [(x, y), (x + 1, y)],
+            Edge::Vertical(x, y) => [(x, y), (x, y + 1)],
+        }
+    }
+
+    pub fn all(grid: &settings::Grid, lattice: Lattice) -> Vec<Edge> {
+        let (columns, rows) = lattice.size(grid);
+        let horizontal = (0..rows)
+            .flat_map(|y| (0..columns.saturating_sub(1)).map(move |x| Edge::Horizontal(x, y)));
+        let vertical = (0..rows.saturating_sub(1))
+            .flat_map(|y| (0..columns).map(move |x| Edge::Vertical(x, y)));
+        horizontal
+            .chain(vertical)
+            .filter(|edge| edge.exists(grid, lattice))
+            .collect()
+    }
+
+    fn exists(&self, grid: &settings::Grid, lattice: Lattice) -> bool {
+        match lattice {
+            Lattice::Centers => self
+                .ends()
+                .iter()
+                .all(|(x, y)| grid.contains(&cells::Cell(*x, *y))),
+            Lattice::Borders => {
+                let sides = match *self {
+                    Edge::Horizontal(x, y) => {
+                        [(x as isize, y as isize - 1), (x as isize, y as isize)]
+                    }
+                    Edge::Vertical(x, y) => {
+                        [(x as isize - 1, y as isize), (x as isize, y as isize)]
+                    }
+                };
+                sides.iter().any(|(x, y)| {
+                    *x >= 0 && *y >= 0 && grid.contains(&cells::Cell(*x as usize, *y as usize))
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_edges() {
+        let grid = settings::Grid::new(1, 2);
+        let expected = vec![Edge::Horizontal(0, 0)];
+        assert_eq!(Edge::all(&grid, Lattice::Centers), expected);
+        assert_eq!(Edge::all(&grid, Lattice::Borders).len(), 7);
+
+        let mut grid = settings::Grid::new(2, 2);
+        grid.missing.insert(cells::Cell(1, 1));
+        assert_eq!(Edge::all(&grid, Lattice::Centers).len(), 2);
+        assert_eq!(Edge::all(&grid, Lattice::Borders).len(), 10);
+    }
+}
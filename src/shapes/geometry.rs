@@ -0,0 +1,72 @@
+use super::cells;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Geometry {
+    #[default]
+    Square,
+    Hex,
+    Triangle,
+}
+
+impl Geometry {
+    pub fn edge_offsets(self, cell: &cells::Cell) -> Vec<(isize, isize)> {
+        match self {
+            Geometry::Square => vec![(0, 1), (1, 0), (0, -1), (-1, 0)],
+            Geometry::Hex => vec![(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)],
+            Geometry::Triangle => {
+                let vertical = if points_up(cell) { 1 } else { -1 };
+                vec![(-1, 0), (1, 0), (0, vertical)]
+            }
+        }
+    }
+
+    pub fn vertex_offsets(self, cell: &cells::Cell) -> Vec<(isize, isize)> {
+        match self {
+            Geometry::Square => vec![
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+            ],
+            Geometry::Hex => self.edge_offsets(cell),
+            Geometry::Triangle => {
+                // The base side touches five cells of the next row, the apex
+                // three cells of the previous one.
+                let (base, apex) = if points_up(cell) { (1, -1) } else { (-1, 1) };
+                let mut result = vec![(-2, 0), (-1, 0), (1, 0), (2, 0)];
+                result.extend((-2..=2).map(|dx| (dx, base)));
+                result.extend((-1..=1).map(|dx| (dx, apex)));
+                result
+            }
+        }
+    }
+}
+
+fn points_up(cell: &cells::Cell) -> bool {
+    (cell.0 + cell.1).is_multiple_of(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triangle_offsets() {
+        let up = cells::Cell(2, 2);
+        let down = cells::Cell(3, 2);
+        assert_eq!(
+            Geometry::Triangle.edge_offsets(&up),
+            vec![(-1, 0), (1, 0), (0, 1)]
+        );
+        assert_eq!(
+            Geometry::Triangle.edge_offsets(&down),
+            vec![(-1, 0), (1, 0), (0, -1)]
+        );
+        assert_eq!(Geometry::Triangle.vertex_offsets(&up).len(), 12);
+    }
+}
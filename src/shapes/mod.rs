@@ -1,5 +1,18 @@
 mod cells;
+mod directions;
+mod edges;
+mod geometry;
+mod moves;
+mod paths;
 mod regions;
+mod vertices;
 
 pub use cells::Cell;
+pub use directions::Diagonal;
+pub use edges::Edge;
+pub use edges::Lattice;
+pub use geometry::Geometry;
+pub use moves::Moves;
+pub use paths::Path;
 pub use regions::Region;
+pub use vertices::Vertex;
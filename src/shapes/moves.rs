@@ -0,0 +1,121 @@
+use super::cells;
+use crate::settings;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Moves {
+    Orthogonal,
+    Knight,
+    King,
+    Custom { offsets: Vec<(isize, isize)> },
+}
+
+impl Moves {
+    pub fn offsets(&self) -> Vec<(isize, isize)> {
+        match self {
+            Moves::Orthogonal => vec![(0, 1), (1, 0), (0, -1), (-1, 0)],
+            Moves::Knight => vec![
+                (1, 2),
+                (2, 1),
+                (2, -1),
+                (1, -2),
+                (-1, -2),
+                (-2, -1),
+                (-2, 1),
+                (-1, 2),
+            ],
+            Moves::King => vec![
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+            ],
+            Moves::Custom { offsets } => offsets.clone(),
+        }
+    }
+
+    pub fn neighbours(&self, cell: &cells::Cell, grid: &settings::Grid) -> Vec<cells::Cell> {
+        let offsets = match self {
+            Moves::Orthogonal => grid.geometry.edge_offsets(cell),
+            Moves::King => grid.geometry.vertex_offsets(cell),
+            _ => self.offsets(),
+        };
+        let mut result = offsets
+            .into_iter()
+            .filter_map(|(dx, dy)| cell.offset(dx, dy, grid))
+            .filter(|other| other != cell)
+            .collect::<Vec<_>>();
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    pub fn pairs(&self, grid: &settings::Grid) -> Vec<(cells::Cell, cells::Cell)> {
+        let mut result = std::collections::BTreeSet::new();
+        for y in 0..grid.rows {
+            for x in 0..grid.columns {
+                let cell = cells::Cell(x, y);
+                if !grid.contains(&cell) {
+                    continue;
+                }
+                for other in self.neighbours(&cell, grid) {
+                    if cell < other {
+                        result.insert((cell.clone(), other));
+                    } else {
+                        result.insert((other, cell.clone()));
+                    }
+                }
+            }
+        }
+        result.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knight_pairs() {
+        let grid = settings::Grid::new(2, 3);
+        let result = Moves::Knight.pairs(&grid);
+        let expected = vec![
+            (cells::Cell(0, 0), cells::Cell(2, 1)),
+            (cells::Cell(0, 1), cells::Cell(2, 0)),
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_custom_pairs_deduplicated() {
+        let grid = settings::Grid::new(1, 3);
+        let moves = Moves::Custom {
+            offsets: vec![(1, 0), (-1, 0)],
+        };
+        let result = moves.pairs(&grid);
+        let expected = vec![
+            (cells::Cell(0, 0), cells::Cell(1, 0)),
+            (cells::Cell(1, 0), cells::Cell(2, 0)),
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_torus_neighbours() {
+        let mut grid = settings::Grid::new(3, 4);
+        grid.topology = settings::Topology::Torus;
+        let result = Moves::Orthogonal.neighbours(&cells::Cell(0, 0), &grid);
+        let expected = vec![
+            cells::Cell(0, 1),
+            cells::Cell(0, 2),
+            cells::Cell(1, 0),
+            cells::Cell(3, 0),
+        ];
+        assert_eq!(result, expected);
+        assert_eq!(Moves::King.pairs(&grid).len(), 48);
+    }
+}
@@ -0,0 +1,61 @@
+use super::cells;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Path {
+    pub cells: Vec<cells::Cell>,
+}
+
+impl Path {
+    pub fn new(cells: Vec<cells::Cell>) -> Result<Path, Box<dyn std::error::Error>> {
+        let path = Path { cells };
+        path.validate()?;
+        Ok(path)
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.cells.is_empty() {
+            return Err("path is empty".into());
+        }
+        for (index, cell) in self.cells.iter().enumerate() {
+            if self.cells[index + 1..].contains(cell) {
+                return Err("path goes through the same cell twice".into());
+            }
+        }
+        for pair in self.cells.windows(2) {
+            let dx = (pair[0].0 as isize - pair[1].0 as isize).abs();
+            let dy = (pair[0].1 as isize - pair[1].1 as isize).abs();
+            if dx.max(dy) != 1 {
+                return Err("path cells are not adjacent".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_path() {
+        let cells = vec![cells::Cell(0, 0), cells::Cell(1, 1), cells::Cell(1, 2)];
+        assert!(Path::new(cells).is_ok());
+    }
+
+    #[test]
+    fn test_gap_in_path() {
+        let cells = vec![cells::Cell(0, 0), cells::Cell(2, 0)];
+        assert!(Path::new(cells).is_err());
+    }
+
+    #[test]
+    fn test_loop_in_path() {
+        let cells = vec![
+            cells::Cell(0, 0),
+            cells::Cell(1, 0),
+            cells::Cell(1, 1),
+            cells::Cell(0, 0),
+        ];
+        assert!(Path::new(cells).is_err());
+    }
+}
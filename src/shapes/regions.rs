@@ -1,4 +1,8 @@
 use super::cells;
+use super::directions;
+use super::geometry;
+use super::moves;
+use crate::settings;
 
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize)]
 pub struct Region {
@@ -6,28 +10,64 @@ pub struct Region {
 }
 
 impl Region {
-    fn row(position: usize, size: usize) -> Region {
+    fn row(grid: &settings::Grid, position: usize) -> Region {
         Region {
-            cells: (0..size)
-                .map(move |index| cells::Cell(index, position))
+            cells: (0..grid.columns)
+                .map(|index| cells::Cell(index, position))
+                .filter(|cell| grid.contains(cell))
                 .collect(),
         }
     }
 
-    pub fn rows(count: usize, size: usize) -> impl Iterator<Item = Region> {
-        (0..count).map(move |index| Region::row(index, size))
+    pub fn rows(grid: &settings::Grid) -> impl Iterator<Item = Region> + '_ {
+        (0..grid.rows).map(move |index| Region::row(grid, index))
     }
 
-    fn column(position: usize, size: usize) -> Region {
+    fn column(grid: &settings::Grid, position: usize) -> Region {
         Region {
-            cells: (0..size)
+            cells: (0..grid.rows)
                 .map(|index| cells::Cell(position, index))
+                .filter(|cell| grid.contains(cell))
                 .collect(),
         }
     }
 
-    pub fn columns(count: usize, size: usize) -> impl Iterator<Item = Region> {
-        (0..count).map(move |index| Region::column(index, size))
+    pub fn columns(grid: &settings::Grid) -> impl Iterator<Item = Region> + '_ {
+        (0..grid.columns).map(move |index| Region::column(grid, index))
+    }
+
+    pub fn lines(grid: &settings::Grid) -> Vec<Region> {
+        match grid.geometry {
+            geometry::Geometry::Square => Region::rows(grid).chain(Region::columns(grid)).collect(),
+            geometry::Geometry::Hex => Region::rows(grid)
+                .chain(Region::columns(grid))
+                .chain(Region::grouped(grid, |cell| (cell.0 + cell.1) as isize))
+                .filter(|region| !region.cells.is_empty())
+                .collect(),
+            geometry::Geometry::Triangle => Region::rows(grid)
+                .chain(Region::grouped(grid, |cell| (cell.0 + cell.1) as isize / 2))
+                .chain(Region::grouped(grid, |cell| {
+                    (cell.0 as isize - cell.1 as isize + 1).div_euclid(2)
+                }))
+                .filter(|region| !region.cells.is_empty())
+                .collect(),
+        }
+    }
+
+    fn grouped(
+        grid: &settings::Grid,
+        key: impl Fn(&cells::Cell) -> isize,
+    ) -> impl Iterator<Item = Region> {
+        let mut groups = std::collections::BTreeMap::new();
+        for y in 0..grid.rows {
+            for x in 0..grid.columns {
+                let cell = cells::Cell(x, y);
+                if grid.contains(&cell) {
+                    groups.entry(key(&cell)).or_insert_with(Vec::new).push(cell);
+                }
+            }
+        }
+        groups.into_values().map(|cells| Region { cells })
     }
 
     fn grid_box(v_offset: usize, h_offset: usize, width: usize, height: usize) -> Region {
@@ -51,16 +91,92 @@ impl Region {
             (0..v_count).map(move |v_offset| Region::grid_box(v_offset, h_offset, width, height))
         })
     }
+
+    pub fn is_connected(&self, grid: &settings::Grid) -> bool {
+        self.components(&moves::Moves::Orthogonal.pairs(grid)).len() <= 1
+    }
+
+    pub fn components(&self, adjacency: &[(cells::Cell, cells::Cell)]) -> Vec<Region> {
+        let members = self.cells.iter().collect::<std::collections::HashSet<_>>();
+        let mut neighbours = std::collections::HashMap::new();
+        for (first, second) in adjacency {
+            if members.contains(first) && members.contains(second) {
+                neighbours
+                    .entry(first)
+                    .or_insert_with(Vec::new)
+                    .push(second);
+                neighbours
+                    .entry(second)
+                    .or_insert_with(Vec::new)
+                    .push(first);
+            }
+        }
+        let mut visited = std::collections::HashSet::new();
+        let mut result = Vec::new();
+        for start in &self.cells {
+            if visited.contains(start) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut to_visit = vec![start];
+            while let Some(cell) = to_visit.pop() {
+                if !visited.insert(cell) {
+                    continue;
+                }
+                component.push(cell.clone());
+                if let Some(others) = neighbours.get(cell) {
+                    to_visit.extend(others.iter().copied());
+                }
+            }
+            component.sort();
+            result.push(Region { cells: component });
+        }
+        result
+    }
+
+    pub fn main_diagonal(size: usize) -> Region {
+        Region {
+            cells: (0..size).map(|index| cells::Cell(index, index)).collect(),
+        }
+    }
+
+    pub fn anti_diagonal(size: usize) -> Region {
+        Region {
+            cells: (0..size)
+                .map(|index| cells::Cell(size - 1 - index, index))
+                .collect(),
+        }
+    }
+
+    pub fn diagonal(
+        grid: &settings::Grid,
+        start: &cells::Cell,
+        direction: directions::Diagonal,
+    ) -> Region {
+        let (dx, dy) = direction.offset();
+        let first = start.offset(0, 0, grid);
+        Region {
+            cells: std::iter::successors(first.clone(), |cell| {
+                cell.offset(dx, dy, grid)
+                    .filter(|next| Some(next) != first.as_ref())
+            })
+            .collect(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::cells;
+    use super::directions;
     use super::Region;
+    use crate::settings;
+    use crate::shapes;
 
     #[test]
     fn test_rows_nominal() {
-        let mut result = Region::rows(3, 3).collect::<Vec<_>>();
+        let grid = settings::Grid::new(3, 3);
+        let mut result = Region::rows(&grid).collect::<Vec<_>>();
         let mut expected = vec![
             Region {
                 cells: vec![cells::Cell(0, 0), cells::Cell(1, 0), cells::Cell(2, 0)],
@@ -79,7 +195,8 @@ mod tests {
 
     #[test]
     fn test_columns_nominal() {
-        let mut result = Region::columns(3, 3).collect::<Vec<_>>();
+        let grid = settings::Grid::new(3, 3);
+        let mut result = Region::columns(&grid).collect::<Vec<_>>();
         let mut expected = vec![
             Region {
                 cells: vec![cells::Cell(0, 0), cells::Cell(0, 1), cells::Cell(0, 2)],
@@ -137,4 +254,132 @@ mod tests {
         expected.sort();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_long_diagonals() {
+        let expected = Region {
+            cells: vec![cells::Cell(0, 0), cells::Cell(1, 1), cells::Cell(2, 2)],
+        };
+        assert_eq!(Region::main_diagonal(3), expected);
+        let expected = Region {
+            cells: vec![cells::Cell(2, 0), cells::Cell(1, 1), cells::Cell(0, 2)],
+        };
+        assert_eq!(Region::anti_diagonal(3), expected);
+    }
+
+    #[test]
+    fn test_diagonal_clipped() {
+        let grid = settings::Grid::new(3, 4);
+        let result = Region::diagonal(&grid, &cells::Cell(1, 2), directions::Diagonal::UpRight);
+        let expected = Region {
+            cells: vec![cells::Cell(1, 2), cells::Cell(2, 1), cells::Cell(3, 0)],
+        };
+        assert_eq!(result, expected);
+        let result = Region::diagonal(&grid, &cells::Cell(4, 0), directions::Diagonal::DownLeft);
+        assert_eq!(result, Region { cells: Vec::new() });
+    }
+
+    #[test]
+    fn test_connected() {
+        let region = Region {
+            cells: vec![cells::Cell(0, 0), cells::Cell(0, 1), cells::Cell(1, 1)],
+        };
+        let grid = settings::Grid::new(2, 2);
+        assert!(region.is_connected(&grid));
+        let region = Region {
+            cells: vec![cells::Cell(0, 0), cells::Cell(1, 1)],
+        };
+        assert!(!region.is_connected(&grid));
+    }
+
+    #[test]
+    fn test_components() {
+        let region = Region {
+            cells: vec![
+                cells::Cell(0, 0),
+                cells::Cell(2, 0),
+                cells::Cell(1, 0),
+                cells::Cell(0, 2),
+            ],
+        };
+        let grid = settings::Grid::new(3, 3);
+        let adjacency = shapes::Moves::Orthogonal.pairs(&grid);
+        let expected = vec![
+            Region {
+                cells: vec![cells::Cell(0, 0), cells::Cell(1, 0), cells::Cell(2, 0)],
+            },
+            Region {
+                cells: vec![cells::Cell(0, 2)],
+            },
+        ];
+        assert_eq!(region.components(&adjacency), expected);
+    }
+
+    #[test]
+    fn test_masked_lines() {
+        let mut grid = settings::Grid::new(2, 3);
+        grid.missing.insert(cells::Cell(1, 0));
+        let rows = Region::rows(&grid).collect::<Vec<_>>();
+        let expected = vec![
+            Region {
+                cells: vec![cells::Cell(0, 0), cells::Cell(2, 0)],
+            },
+            Region {
+                cells: vec![cells::Cell(0, 1), cells::Cell(1, 1), cells::Cell(2, 1)],
+            },
+        ];
+        assert_eq!(rows, expected);
+        let columns = Region::columns(&grid).collect::<Vec<_>>();
+        assert_eq!(columns[1].cells, vec![cells::Cell(1, 1)]);
+    }
+
+    #[test]
+    fn test_diagonal_torus() {
+        let mut grid = settings::Grid::new(3, 3);
+        grid.topology = settings::Topology::Torus;
+        let result = Region::diagonal(&grid, &cells::Cell(1, 0), directions::Diagonal::DownRight);
+        let expected = Region {
+            cells: vec![cells::Cell(1, 0), cells::Cell(2, 1), cells::Cell(0, 2)],
+        };
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_hex_lines() {
+        let grid = settings::Grid::hexagon(1);
+        let lines = Region::lines(&grid);
+        assert_eq!(lines.len(), 9);
+        let expected = Region {
+            cells: vec![cells::Cell(2, 0), cells::Cell(1, 1), cells::Cell(0, 2)],
+        };
+        assert!(lines.contains(&expected));
+    }
+
+    #[test]
+    fn test_triangle_lines() {
+        let grid = settings::Grid::triangle(2);
+        let mut lines = Region::lines(&grid);
+        lines.sort();
+        let expected = vec![
+            Region {
+                cells: vec![cells::Cell(1, 1)],
+            },
+            Region {
+                cells: vec![cells::Cell(1, 1), cells::Cell(2, 1), cells::Cell(3, 1)],
+            },
+            Region {
+                cells: vec![cells::Cell(2, 0)],
+            },
+            Region {
+                cells: vec![cells::Cell(2, 0), cells::Cell(1, 1), cells::Cell(2, 1)],
+            },
+            Region {
+                cells: vec![cells::Cell(2, 0), cells::Cell(2, 1), cells::Cell(3, 1)],
+            },
+            Region {
+                cells: vec![cells::Cell(3, 1)],
+            },
+        ];
+        assert_eq!(lines, expected);
+    }
 }
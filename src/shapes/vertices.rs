@@ -0,0 +1,43 @@
+use super::cells;
+use crate::settings;
+
+#[derive(
+    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+pub struct Vertex(pub usize, pub usize);
+
+impl Vertex {
+    pub fn cells(&self, grid: &settings::Grid) -> Vec<cells::Cell> {
+        [(1, 1), (0, 1), (1, 0), (0, 0)]
+            .iter()
+            .filter(|(dx, dy)| self.0 >= *dx && self.1 >= *dy)
+            .map(|(dx, dy)| cells::Cell(self.0 - dx, self.1 - dy))
+            .filter(|cell| grid.contains(cell))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inner_vertex() {
+        let grid = settings::Grid::new(3, 3);
+        let expected = vec![
+            cells::Cell(0, 0),
+            cells::Cell(1, 0),
+            cells::Cell(0, 1),
+            cells::Cell(1, 1),
+        ];
+        assert_eq!(Vertex(1, 1).cells(&grid), expected);
+    }
+
+    #[test]
+    fn test_border_vertices() {
+        let grid = settings::Grid::new(3, 3);
+        assert_eq!(Vertex(0, 0).cells(&grid), vec![cells::Cell(0, 0)]);
+        let expected = vec![cells::Cell(2, 0), cells::Cell(2, 1)];
+        assert_eq!(Vertex(3, 1).cells(&grid), expected);
+    }
+}
@@ -6,13 +6,126 @@ use itertools::Itertools;
 pub fn apply_constraints(state: &mut states::State, constraints: &[settings::Constraint]) {
     states::apply_constraints(state, constraints);
     for constraint in constraints {
-        if let settings::Constraint::SymbolRepartition {
-            tokenset,
-            region,
-            repartition,
-        } = constraint
-        {
-            apply_symbol_repartition(state, *tokenset, region, repartition);
+        match constraint {
+            settings::Constraint::SymbolRepartition {
+                tokenset,
+                region,
+                repartition,
+            } => apply_symbol_repartition(state, *tokenset, region, repartition),
+            settings::Constraint::RegionSum {
+                tokenset,
+                region,
+                values,
+                sum,
+                distinct,
+            } => apply_region_sum(state, *tokenset, region, values, *sum, *distinct),
+            settings::Constraint::Different {
+                tokenset,
+                first,
+                second,
+            } => {
+                apply_different(state, *tokenset, first, second);
+                apply_different(state, *tokenset, second, first);
+            }
+            settings::Constraint::NonConsecutive {
+                tokenset,
+                first,
+                second,
+                order,
+            } => {
+                apply_non_consecutive(state, *tokenset, first, second, order);
+                apply_non_consecutive(state, *tokenset, second, first, order);
+            }
+            settings::Constraint::Less {
+                tokenset,
+                lower,
+                greater,
+                order,
+            } => apply_less(state, *tokenset, lower, greater, order),
+            settings::Constraint::Renban {
+                tokenset,
+                region,
+                order,
+            } => apply_renban(state, *tokenset, region, order),
+            settings::Constraint::MinDifference {
+                tokenset,
+                first,
+                second,
+                values,
+                difference,
+            } => {
+                apply_min_difference(state, *tokenset, first, second, values, *difference);
+                apply_min_difference(state, *tokenset, second, first, values, *difference);
+            }
+            settings::Constraint::SameSymbol {
+                tokenset,
+                first,
+                second,
+            } => {
+                apply_same_symbol(state, *tokenset, first, second);
+                apply_same_symbol(state, *tokenset, second, first);
+            }
+            settings::Constraint::EqualSums {
+                tokenset,
+                regions,
+                values,
+            } => apply_equal_sums(state, *tokenset, regions, values),
+            settings::Constraint::Between {
+                tokenset,
+                ends,
+                region,
+                order,
+            } => apply_between(state, *tokenset, ends, region, order),
+            settings::Constraint::DistinctClasses {
+                tokenset,
+                region,
+                classes,
+            } => apply_distinct_classes(state, *tokenset, region, classes),
+            settings::Constraint::MustContain {
+                tokenset,
+                region,
+                symbols,
+            } => apply_must_contain(state, *tokenset, region, symbols),
+            settings::Constraint::ShadeCount {
+                tokenset,
+                region,
+                count,
+            } => apply_shade_count(state, *tokenset, region, *count),
+            settings::Constraint::NotBothShaded {
+                tokenset,
+                first,
+                second,
+            } => {
+                apply_not_both_shaded(state, *tokenset, first, second);
+                apply_not_both_shaded(state, *tokenset, second, first);
+            }
+            settings::Constraint::NotAllShaded { tokenset, region } => {
+                apply_not_all_shaded(state, *tokenset, region)
+            }
+            settings::Constraint::ShadedConnected {
+                tokenset,
+                region,
+                adjacency,
+            } => apply_shaded_connected(state, *tokenset, region, adjacency),
+            settings::Constraint::NumberedIslands {
+                tokenset,
+                region,
+                adjacency,
+                clues,
+            } => apply_numbered_islands(state, *tokenset, region, adjacency, clues),
+            settings::Constraint::SingleLoop { tokenset, edges } => {
+                apply_single_loop(state, *tokenset, edges)
+            }
+            settings::Constraint::SharedCell {
+                tokenset,
+                cell,
+                other_tokenset,
+                other_cell,
+            } => {
+                apply_shared_cell(state, *tokenset, cell, *other_tokenset, other_cell);
+                apply_shared_cell(state, *other_tokenset, other_cell, *tokenset, cell);
+            }
+            _ => (),
         }
     }
 }
@@ -24,38 +137,715 @@ fn apply_symbol_repartition(
     repartition: &settings::SymbolRepartition,
 ) {
     let tokenset = &mut state.tokensets[tokenset];
-    match tokenset {
-        states::Tokenset::Symbols(cells) => {
-            let mut found = settings::SymbolRepartition::new();
-            for cell in &region.cells {
-                let cell_state = &cells[cell];
-                if let states::CellState::Set(value) = cell_state {
-                    found
-                        .entry(*value)
-                        .and_modify(|count| *count += 1)
-                        .or_insert(1);
-                }
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let mut found = settings::SymbolRepartition::new();
+    for cell in &region.cells {
+        let cell_state = &cells[cell];
+        if let states::CellState::Set(value) = cell_state {
+            found
+                .entry(*value)
+                .and_modify(|count| *count += 1)
+                .or_insert(1);
+        }
+    }
+    let to_remove = repartition
+        .iter()
+        .filter_map(|(symbol, expected_count)| {
+            let count = found.get(symbol).unwrap_or(&0);
+            if count == expected_count {
+                Some(symbol)
+            } else {
+                None
             }
-            let to_remove = repartition
+        })
+        .collect::<Vec<_>>();
+    for cell in &region.cells {
+        let cell_state = &mut cells[cell];
+        if let states::CellState::Candidates(candidates) = cell_state {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| !to_remove.contains(&candidate))
+                .join("");
+        }
+    }
+}
+
+fn apply_region_sum(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    values: &settings::SymbolValues,
+    sum: usize,
+    distinct: bool,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let bounds = region
+        .cells
+        .iter()
+        .map(|cell| {
+            let possible_values: Vec<usize> = match &cells[cell] {
+                states::CellState::Set(value) => values.get(value).cloned().into_iter().collect(),
+                states::CellState::Candidates(candidates) => candidates
+                    .chars()
+                    .filter_map(|candidate| values.get(&candidate).cloned())
+                    .collect(),
+                states::CellState::Empty => values.values().cloned().collect(),
+            };
+            (
+                possible_values.iter().cloned().min().unwrap_or(0),
+                possible_values.iter().cloned().max().unwrap_or(0),
+            )
+        })
+        .collect::<Vec<_>>();
+    let min_sum = bounds.iter().map(|(min, _)| min).sum::<usize>();
+    let max_sum = bounds.iter().map(|(_, max)| max).sum::<usize>();
+    let set_symbols = region
+        .cells
+        .iter()
+        .filter_map(|cell| match &cells[cell] {
+            states::CellState::Set(value) => Some(*value),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for (cell, (min, max)) in region.cells.iter().zip(bounds) {
+        let cell_state = &mut cells[cell];
+        if let states::CellState::Candidates(candidates) = cell_state {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| {
+                    let value = values.get(candidate).cloned().unwrap_or(0);
+                    min_sum - min + value <= sum
+                        && max_sum - max + value >= sum
+                        && !(distinct && set_symbols.contains(candidate))
+                })
+                .join("");
+        }
+    }
+    if distinct {
+        apply_sum_combinations(cells, region, values, sum);
+    }
+}
+
+// Long regions over large alphabets are left to the bounds check.
+const MAX_SUM_COMBINATIONS: usize = 10_000;
+
+fn apply_sum_combinations(
+    cells: &mut states::CellGrid,
+    region: &shapes::Region,
+    values: &settings::SymbolValues,
+    sum: usize,
+) {
+    let options = region
+        .cells
+        .iter()
+        .map(|cell| match &cells[cell] {
+            states::CellState::Set(value) => vec![*value],
+            states::CellState::Candidates(candidates) => candidates.chars().collect(),
+            states::CellState::Empty => values.keys().cloned().collect(),
+        })
+        .collect::<Vec<_>>();
+    let symbols = options
+        .iter()
+        .flatten()
+        .cloned()
+        .unique()
+        .sorted()
+        .collect::<Vec<_>>();
+    if combination_count(symbols.len(), region.cells.len()) > MAX_SUM_COMBINATIONS {
+        return;
+    }
+    let combinations = symbols
+        .into_iter()
+        .combinations(region.cells.len())
+        .filter(|combination| {
+            combination
+                .iter()
+                .map(|symbol| values.get(symbol).cloned().unwrap_or(0))
+                .sum::<usize>()
+                == sum
+        })
+        .filter(|combination| {
+            options
+                .iter()
+                .all(|option| option.iter().any(|symbol| combination.contains(symbol)))
+        })
+        .collect::<Vec<_>>();
+    for cell in &region.cells {
+        if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| {
+                    combinations
+                        .iter()
+                        .any(|combination| combination.contains(candidate))
+                })
+                .join("");
+        }
+    }
+}
+
+fn combination_count(symbols: usize, cells: usize) -> usize {
+    if cells > symbols {
+        return 0;
+    }
+    (0..cells).fold(1, |count: usize, index| {
+        count.saturating_mul(symbols - index) / (index + 1)
+    })
+}
+
+fn apply_different(
+    state: &mut states::State,
+    tokenset: usize,
+    source: &shapes::Cell,
+    target: &shapes::Cell,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    if let states::CellState::Set(value) = cells[source].clone() {
+        if let states::CellState::Candidates(candidates) = &mut cells[target] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| *candidate != value)
+                .join("");
+        }
+    }
+}
+
+fn apply_non_consecutive(
+    state: &mut states::State,
+    tokenset: usize,
+    source: &shapes::Cell,
+    target: &shapes::Cell,
+    order: &settings::SymbolOrder,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    if let states::CellState::Set(value) = cells[source].clone() {
+        if let states::CellState::Candidates(candidates) = &mut cells[target] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| !settings::are_consecutive(order, *candidate, value))
+                .join("");
+        }
+    }
+}
+
+fn apply_less(
+    state: &mut states::State,
+    tokenset: usize,
+    lower: &shapes::Cell,
+    greater: &shapes::Cell,
+    order: &settings::SymbolOrder,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let ranks = |cell_state: &states::CellState| -> Vec<usize> {
+        match cell_state {
+            states::CellState::Set(value) => order.get(value).cloned().into_iter().collect(),
+            states::CellState::Candidates(candidates) => candidates
+                .chars()
+                .filter_map(|candidate| order.get(&candidate).cloned())
+                .collect(),
+            states::CellState::Empty => Vec::new(),
+        }
+    };
+    if let Some(max) = ranks(&cells[greater]).into_iter().max() {
+        if let states::CellState::Candidates(candidates) = &mut cells[lower] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| order.get(candidate).is_some_and(|rank| *rank < max))
+                .join("");
+        }
+    }
+    if let Some(min) = ranks(&cells[lower]).into_iter().min() {
+        if let states::CellState::Candidates(candidates) = &mut cells[greater] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| order.get(candidate).is_some_and(|rank| *rank > min))
+                .join("");
+        }
+    }
+}
+
+fn possible_symbols(
+    cell_state: &states::CellState,
+    all_symbols: &[settings::SymbolType],
+) -> Vec<settings::SymbolType> {
+    match cell_state {
+        states::CellState::Set(value) => vec![*value],
+        states::CellState::Candidates(candidates) => candidates.chars().collect(),
+        states::CellState::Empty => all_symbols.to_vec(),
+    }
+}
+
+fn apply_renban(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    order: &settings::SymbolOrder,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let set_symbols = region
+        .cells
+        .iter()
+        .filter_map(|cell| match &cells[cell] {
+            states::CellState::Set(value) => Some(*value),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let set_ranks = set_symbols
+        .iter()
+        .filter_map(|symbol| order.get(symbol))
+        .collect::<Vec<_>>();
+    let length = region.cells.len();
+    let fits_window = |rank: usize| {
+        (0..order.len())
+            .filter(|start| start + length <= order.len())
+            .any(|start| {
+                let window = start..start + length;
+                window.contains(&rank) && set_ranks.iter().all(|set| window.contains(set))
+            })
+    };
+    for cell in &region.cells {
+        if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| {
+                    !set_symbols.contains(candidate)
+                        && order.get(candidate).is_some_and(|rank| fits_window(*rank))
+                })
+                .join("");
+        }
+    }
+}
+
+fn apply_min_difference(
+    state: &mut states::State,
+    tokenset: usize,
+    source: &shapes::Cell,
+    target: &shapes::Cell,
+    values: &settings::SymbolValues,
+    difference: usize,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let all_symbols = values.keys().cloned().collect::<Vec<_>>();
+    let source_values = possible_symbols(&cells[source], &all_symbols)
+        .iter()
+        .filter_map(|symbol| values.get(symbol))
+        .map(|value| *value as isize)
+        .collect::<Vec<_>>();
+    if let states::CellState::Candidates(candidates) = &mut cells[target] {
+        *candidates = candidates
+            .chars()
+            .filter(|candidate| {
+                values.get(candidate).is_some_and(|value| {
+                    source_values
+                        .iter()
+                        .any(|other| (*value as isize - other).abs() >= difference as isize)
+                })
+            })
+            .join("");
+    }
+}
+
+fn apply_same_symbol(
+    state: &mut states::State,
+    tokenset: usize,
+    source: &shapes::Cell,
+    target: &shapes::Cell,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let source_symbols = match &cells[source] {
+        states::CellState::Set(value) => value.to_string(),
+        states::CellState::Candidates(candidates) => candidates.clone(),
+        states::CellState::Empty => return,
+    };
+    if let states::CellState::Candidates(candidates) = &mut cells[target] {
+        *candidates = candidates
+            .chars()
+            .filter(|candidate| source_symbols.contains(*candidate))
+            .join("");
+    }
+}
+
+fn apply_shared_cell(
+    state: &mut states::State,
+    source_tokenset: usize,
+    source: &shapes::Cell,
+    target_tokenset: usize,
+    target: &shapes::Cell,
+) {
+    let source_symbols = match &state.tokensets[source_tokenset] {
+        states::Tokenset::Symbols(cells) => match &cells[source] {
+            states::CellState::Set(value) => value.to_string(),
+            states::CellState::Candidates(candidates) => candidates.clone(),
+            states::CellState::Empty => return,
+        },
+        _ => return,
+    };
+    let states::Tokenset::Symbols(cells) = &mut state.tokensets[target_tokenset] else {
+        return;
+    };
+    if let states::CellState::Candidates(candidates) = &mut cells[target] {
+        *candidates = candidates
+            .chars()
+            .filter(|candidate| source_symbols.contains(*candidate))
+            .join("");
+    }
+}
+
+fn apply_shade_count(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    count: usize,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Shading(cells) = tokenset else {
+        return;
+    };
+    let shaded = region
+        .cells
+        .iter()
+        .filter(|cell| cells[*cell] == states::ShadeState::Shaded)
+        .count();
+    let unknown = region
+        .cells
+        .iter()
+        .filter(|cell| cells[*cell] == states::ShadeState::Unknown)
+        .collect::<Vec<_>>();
+    let shade_state = if shaded == count {
+        states::ShadeState::Unshaded
+    } else if shaded + unknown.len() == count {
+        states::ShadeState::Shaded
+    } else {
+        return;
+    };
+    for cell in unknown {
+        cells[cell] = shade_state;
+    }
+}
+
+fn apply_not_both_shaded(
+    state: &mut states::State,
+    tokenset: usize,
+    source: &shapes::Cell,
+    target: &shapes::Cell,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Shading(cells) = tokenset else {
+        return;
+    };
+    if cells[source] == states::ShadeState::Shaded && cells[target] == states::ShadeState::Unknown {
+        cells[target] = states::ShadeState::Unshaded;
+    }
+}
+
+fn apply_not_all_shaded(state: &mut states::State, tokenset: usize, region: &shapes::Region) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Shading(cells) = tokenset else {
+        return;
+    };
+    let unknown = region
+        .cells
+        .iter()
+        .filter(|cell| cells[*cell] != states::ShadeState::Shaded)
+        .collect::<Vec<_>>();
+    if let [cell] = unknown[..] {
+        if cells[cell] == states::ShadeState::Unknown {
+            cells[cell] = states::ShadeState::Unshaded;
+        }
+    }
+}
+
+fn apply_shaded_connected(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    adjacency: &[(shapes::Cell, shapes::Cell)],
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Shading(cells) = tokenset else {
+        return;
+    };
+    let open = shapes::Region {
+        cells: region
+            .cells
+            .iter()
+            .filter(|cell| cells[*cell] != states::ShadeState::Unshaded)
+            .cloned()
+            .collect(),
+    };
+    let (shaded, unreachable): (Vec<_>, Vec<_>) =
+        open.components(adjacency)
+            .into_iter()
+            .partition(|component| {
+                component
+                    .cells
+                    .iter()
+                    .any(|cell| cells[cell] == states::ShadeState::Shaded)
+            });
+    // Once the shaded cells share a single area, unknown cells elsewhere
+    // cannot be shaded without breaking connectivity.
+    if shaded.len() != 1 {
+        return;
+    }
+    for cell in unreachable.iter().flat_map(|component| &component.cells) {
+        cells[cell] = states::ShadeState::Unshaded;
+    }
+}
+
+fn apply_numbered_islands(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    adjacency: &[(shapes::Cell, shapes::Cell)],
+    clues: &[(shapes::Cell, usize)],
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Shading(cells) = tokenset else {
+        return;
+    };
+    let islands = shapes::Region {
+        cells: region
+            .cells
+            .iter()
+            .filter(|cell| cells[*cell] == states::ShadeState::Unshaded)
+            .cloned()
+            .collect(),
+    };
+    let mut to_shade = std::collections::HashSet::new();
+    let mut touched = std::collections::HashMap::new();
+    for (index, island) in islands.components(adjacency).iter().enumerate() {
+        let sizes = clues
+            .iter()
+            .filter(|(cell, _)| island.cells.contains(cell))
+            .map(|(_, size)| *size)
+            .collect::<Vec<_>>();
+        let [size] = sizes[..] else {
+            continue;
+        };
+        for (first, second) in adjacency {
+            let outside = if island.cells.contains(first) {
+                second
+            } else if island.cells.contains(second) {
+                first
+            } else {
+                continue;
+            };
+            if cells[outside] != states::ShadeState::Unknown {
+                continue;
+            }
+            // A complete island is surrounded by shaded cells, and a cell
+            // touching two numbered islands would merge them.
+            let previous = touched.insert(outside.clone(), index);
+            if island.cells.len() == size || previous.is_some_and(|other| other != index) {
+                to_shade.insert(outside.clone());
+            }
+        }
+    }
+    for cell in to_shade {
+        cells[&cell] = states::ShadeState::Shaded;
+    }
+}
+
+fn apply_single_loop(state: &mut states::State, tokenset: usize, edges: &[shapes::Edge]) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Edges(cells) = tokenset else {
+        return;
+    };
+    let mut incident = std::collections::BTreeMap::new();
+    for edge in edges {
+        for end in edge.ends().iter() {
+            incident.entry(*end).or_insert_with(Vec::new).push(edge);
+        }
+    }
+    for point_edges in incident.values() {
+        let on = point_edges
+            .iter()
+            .filter(|edge| cells[**edge] == states::EdgeState::On)
+            .count();
+        let unknown = point_edges
+            .iter()
+            .filter(|edge| cells[**edge] == states::EdgeState::Unknown)
+            .collect::<Vec<_>>();
+        // Loop points have either no edge or exactly two.
+        let edge_state = match (on, unknown.len()) {
+            (2, _) | (0, 1) => states::EdgeState::Off,
+            (1, 1) => states::EdgeState::On,
+            _ => continue,
+        };
+        for edge in unknown {
+            cells[*edge] = edge_state;
+        }
+    }
+}
+
+fn apply_equal_sums(
+    state: &mut states::State,
+    tokenset: usize,
+    regions: &[shapes::Region],
+    values: &settings::SymbolValues,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let all_symbols = values.keys().cloned().collect::<Vec<_>>();
+    let bounds = |cells: &states::CellGrid, cell: &shapes::Cell| {
+        let possible_values = possible_symbols(&cells[cell], &all_symbols)
+            .iter()
+            .filter_map(|symbol| values.get(symbol).cloned())
+            .collect::<Vec<_>>();
+        (
+            possible_values.iter().cloned().min().unwrap_or(0),
+            possible_values.iter().cloned().max().unwrap_or(0),
+        )
+    };
+    let region_bounds = regions
+        .iter()
+        .map(|region| {
+            region
+                .cells
                 .iter()
-                .filter_map(|(symbol, expected_count)| {
-                    let count = found.get(symbol).unwrap_or(&0);
-                    if count == expected_count {
-                        Some(symbol)
-                    } else {
-                        None
-                    }
+                .map(|cell| bounds(cells, cell))
+                .fold((0, 0), |(min, max), (cell_min, cell_max)| {
+                    (min + cell_min, max + cell_max)
+                })
+        })
+        .collect::<Vec<_>>();
+    let low = region_bounds.iter().map(|(min, _)| *min).max().unwrap_or(0);
+    let high = region_bounds.iter().map(|(_, max)| *max).min().unwrap_or(0);
+    for (region, (min_sum, max_sum)) in regions.iter().zip(region_bounds) {
+        for cell in &region.cells {
+            let (min, max) = bounds(cells, cell);
+            if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+                *candidates = candidates
+                    .chars()
+                    .filter(|candidate| {
+                        let value = values.get(candidate).cloned().unwrap_or(0);
+                        min_sum - min + value <= high && max_sum - max + value >= low
+                    })
+                    .join("");
+            }
+        }
+    }
+}
+
+fn apply_between(
+    state: &mut states::State,
+    tokenset: usize,
+    ends: &(shapes::Cell, shapes::Cell),
+    region: &shapes::Region,
+    order: &settings::SymbolOrder,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let all_symbols = order.keys().cloned().collect::<Vec<_>>();
+    let end_ranks = possible_symbols(&cells[&ends.0], &all_symbols)
+        .into_iter()
+        .chain(possible_symbols(&cells[&ends.1], &all_symbols))
+        .filter_map(|symbol| order.get(&symbol).cloned())
+        .collect::<Vec<_>>();
+    let (low, high) = match (end_ranks.iter().min(), end_ranks.iter().max()) {
+        (Some(low), Some(high)) => (*low, *high),
+        _ => return,
+    };
+    for cell in &region.cells {
+        if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| {
+                    order
+                        .get(candidate)
+                        .is_some_and(|rank| low < *rank && *rank < high)
                 })
-                .collect::<Vec<_>>();
-            for cell in &region.cells {
-                let cell_state = &mut cells[cell];
-                if let states::CellState::Candidates(candidates) = cell_state {
-                    *candidates = candidates
-                        .chars()
-                        .filter(|candidate| !to_remove.contains(&candidate))
-                        .join("");
+                .join("");
+        }
+    }
+}
+
+fn apply_distinct_classes(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    classes: &[String],
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let set_classes = region
+        .cells
+        .iter()
+        .filter_map(|cell| match &cells[cell] {
+            states::CellState::Set(value) => classes.iter().find(|class| class.contains(*value)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for cell in &region.cells {
+        if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| !set_classes.iter().any(|class| class.contains(*candidate)))
+                .join("");
+        }
+    }
+}
+
+fn apply_must_contain(
+    state: &mut states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    symbols: &str,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    let mut missing = symbols.chars().collect::<Vec<_>>();
+    let mut unset = 0;
+    for cell in &region.cells {
+        match &cells[cell] {
+            states::CellState::Set(value) => {
+                if let Some(index) = missing.iter().position(|symbol| symbol == value) {
+                    missing.remove(index);
                 }
             }
+            _ => unset += 1,
+        }
+    }
+    if missing.len() != unset {
+        return;
+    }
+    for cell in &region.cells {
+        if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+            *candidates = candidates
+                .chars()
+                .filter(|candidate| missing.contains(candidate))
+                .join("");
         }
     }
 }
@@ -69,7 +859,7 @@ mod tests {
     use crate::shapes;
     use crate::states;
 
-    fn sample_setting() -> settings::PuzzleSetting {
+    fn sample_setting() -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
         let mut setting = settings::PuzzleSetting::default();
         let grid = setting.add_grid(4, 4);
         let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
@@ -79,8 +869,8 @@ mod tests {
             ".1..".to_string(),
             "....".to_string(),
         ]);
-        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens);
-        setting
+        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens)?;
+        Ok(setting)
     }
 
     fn candidates(symbols: &str) -> states::CellState {
@@ -89,7 +879,7 @@ mod tests {
 
     #[test]
     fn test_start_position() -> Result<(), Box<dyn std::error::Error>> {
-        let setting = sample_setting();
+        let setting = sample_setting()?;
         let constraints = settings::list_constraints(&setting)?;
         let mut state = states::State {
             tokensets: vec![states::symbolset(
@@ -133,4 +923,380 @@ mod tests {
         assert_eq!(state, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_region_sum() {
+        let constraints = vec![settings::Constraint::RegionSum {
+            tokenset: 0,
+            region: shapes::Region {
+                cells: (0..3).map(|x| shapes::Cell(x, 0)).collect(),
+            },
+            values: settings::digit_values("1234"),
+            sum: 5,
+            distinct: true,
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(&["1...".to_string()], candidates("1234"))],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            states::CellState::Set('1'),
+            candidates(""),
+            candidates(""),
+            candidates("1234"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_combination_count() {
+        assert_eq!(combination_count(9, 4), 126);
+        assert_eq!(combination_count(25, 12), 5_200_300);
+        assert_eq!(combination_count(3, 4), 0);
+    }
+
+    #[test]
+    fn test_sum_combinations() {
+        let constraints = vec![settings::Constraint::RegionSum {
+            tokenset: 0,
+            region: shapes::Region {
+                cells: (0..2).map(|x| shapes::Cell(x, 0)).collect(),
+            },
+            values: settings::digit_values("123456789"),
+            sum: 4,
+            distinct: true,
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(
+                &["..".to_string()],
+                candidates("123456789"),
+            )],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            candidates("13"),
+            candidates("13"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_non_consecutive() {
+        let order = "1234"
+            .chars()
+            .enumerate()
+            .map(|(rank, symbol)| (symbol, rank))
+            .collect::<settings::SymbolOrder>();
+        let constraints = vec![settings::Constraint::NonConsecutive {
+            tokenset: 0,
+            first: shapes::Cell(0, 0),
+            second: shapes::Cell(1, 0),
+            order,
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(&["3.".to_string()], candidates("1234"))],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            states::CellState::Set('3'),
+            candidates("13"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_less() {
+        let order = "1234"
+            .chars()
+            .enumerate()
+            .map(|(rank, symbol)| (symbol, rank))
+            .collect::<settings::SymbolOrder>();
+        let constraints = vec![settings::Constraint::Less {
+            tokenset: 0,
+            lower: shapes::Cell(0, 0),
+            greater: shapes::Cell(1, 0),
+            order,
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                candidates("234"),
+                candidates("134"),
+            ]])],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            candidates("23"),
+            candidates("34"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_german_whispers() {
+        let constraints = vec![settings::Constraint::MinDifference {
+            tokenset: 0,
+            first: shapes::Cell(0, 0),
+            second: shapes::Cell(1, 0),
+            values: settings::digit_values("123456789"),
+            difference: 5,
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                candidates("123456789"),
+                candidates("34"),
+            ]])],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            candidates("89"),
+            candidates("34"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_renban() {
+        let constraints = vec![settings::Constraint::Renban {
+            tokenset: 0,
+            region: shapes::Region {
+                cells: (0..3).map(|x| shapes::Cell(x, 0)).collect(),
+            },
+            order: "123456789"
+                .chars()
+                .enumerate()
+                .map(|(rank, symbol)| (symbol, rank))
+                .collect(),
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(
+                &["7..".to_string()],
+                candidates("123456789"),
+            )],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            states::CellState::Set('7'),
+            candidates("5689"),
+            candidates("5689"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_between() {
+        let constraints = vec![settings::Constraint::Between {
+            tokenset: 0,
+            ends: (shapes::Cell(0, 0), shapes::Cell(3, 0)),
+            region: shapes::Region {
+                cells: vec![shapes::Cell(1, 0), shapes::Cell(2, 0)],
+            },
+            order: "123456"
+                .chars()
+                .enumerate()
+                .map(|(rank, symbol)| (symbol, rank))
+                .collect(),
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                candidates("23"),
+                candidates("123456"),
+                candidates("123456"),
+                candidates("56"),
+            ]])],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            candidates("23"),
+            candidates("345"),
+            candidates("345"),
+            candidates("56"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_equal_sums() {
+        let constraints = vec![settings::Constraint::EqualSums {
+            tokenset: 0,
+            regions: vec![
+                shapes::Region {
+                    cells: vec![shapes::Cell(0, 0), shapes::Cell(1, 0)],
+                },
+                shapes::Region {
+                    cells: vec![shapes::Cell(2, 0)],
+                },
+            ],
+            values: settings::digit_values("1234"),
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                candidates("1234"),
+                candidates("1234"),
+                candidates("123"),
+            ]])],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![vec![
+            candidates("12"),
+            candidates("12"),
+            candidates("23"),
+        ]])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_must_contain() {
+        let constraints = vec![settings::Constraint::MustContain {
+            tokenset: 0,
+            region: shapes::Region {
+                cells: vec![
+                    shapes::Cell(0, 0),
+                    shapes::Cell(1, 0),
+                    shapes::Cell(0, 1),
+                    shapes::Cell(1, 1),
+                ],
+            },
+            symbols: "1237".to_string(),
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::symbolset(
+                &["2.".to_string(), "..".to_string()],
+                candidates("123456789"),
+            )],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![states::Tokenset::Symbols(vec![
+            vec![states::CellState::Set('2'), candidates("137")],
+            vec![candidates("137"), candidates("137")],
+        ])];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_shared_cell() {
+        let constraints = vec![
+            settings::Constraint::SharedCell {
+                tokenset: 0,
+                cell: shapes::Cell(1, 0),
+                other_tokenset: 1,
+                other_cell: shapes::Cell(0, 0),
+            },
+            settings::Constraint::SharedCell {
+                tokenset: 0,
+                cell: shapes::Cell(0, 0),
+                other_tokenset: 1,
+                other_cell: shapes::Cell(1, 0),
+            },
+        ];
+        let mut state = states::State {
+            tokensets: vec![
+                states::Tokenset::Symbols(vec![vec![candidates("12"), candidates("123")]]),
+                states::Tokenset::Symbols(vec![vec![
+                    candidates("234"),
+                    states::CellState::Set('1'),
+                ]]),
+            ],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![
+            states::Tokenset::Symbols(vec![vec![states::CellState::Set('1'), candidates("23")]]),
+            states::Tokenset::Symbols(vec![vec![candidates("23"), states::CellState::Set('1')]]),
+        ];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_single_loop() {
+        let edges = vec![
+            shapes::Edge::Horizontal(0, 0),
+            shapes::Edge::Horizontal(1, 0),
+            shapes::Edge::Horizontal(0, 1),
+            shapes::Edge::Horizontal(1, 1),
+            shapes::Edge::Vertical(0, 0),
+            shapes::Edge::Vertical(1, 0),
+            shapes::Edge::Vertical(2, 0),
+        ];
+        let constraints = vec![settings::Constraint::SingleLoop { tokenset: 0, edges }];
+        let mut state = states::State {
+            tokensets: vec![states::edgeset(
+                &["..".to_string(), "x.".to_string()],
+                &["#..".to_string()],
+            )],
+        };
+        apply_constraints(&mut state, &constraints);
+        let expected = states::edgeset(&["#.".to_string(), "x.".to_string()], &["#..".to_string()]);
+        assert_eq!(state.tokensets[0], expected);
+    }
+
+    #[test]
+    fn test_shade_count() {
+        let region = shapes::Region {
+            cells: (0..3).map(|x| shapes::Cell(x, 0)).collect(),
+        };
+        let constraints = vec![
+            settings::Constraint::ShadeCount {
+                tokenset: 0,
+                region: region.clone(),
+                count: 1,
+            },
+            settings::Constraint::ShadeCount {
+                tokenset: 1,
+                region,
+                count: 1,
+            },
+        ];
+        let mut state = states::State {
+            tokensets: vec![
+                states::shadeset(&["#..".to_string()]),
+                states::shadeset(&["oo.".to_string()]),
+            ],
+        };
+        apply_constraints(&mut state, &constraints);
+        let tokensets = vec![
+            states::shadeset(&["#oo".to_string()]),
+            states::shadeset(&["oo#".to_string()]),
+        ];
+        assert_eq!(state, states::State { tokensets });
+    }
+
+    #[test]
+    fn test_not_both_shaded() {
+        let constraints = vec![settings::Constraint::NotBothShaded {
+            tokenset: 0,
+            first: shapes::Cell(1, 0),
+            second: shapes::Cell(0, 1),
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::shadeset(&["o#".to_string(), "..".to_string()])],
+        };
+        apply_constraints(&mut state, &constraints);
+        let expected = states::shadeset(&["o#".to_string(), "o.".to_string()]);
+        assert_eq!(state.tokensets[0], expected);
+    }
+
+    #[test]
+    fn test_numbered_islands() {
+        let grid = settings::Grid::new(3, 3);
+        let region = shapes::Region {
+            cells: shapes::Region::rows(&grid)
+                .flat_map(|row| row.cells)
+                .collect(),
+        };
+        let constraints = vec![settings::Constraint::NumberedIslands {
+            tokenset: 0,
+            region,
+            adjacency: shapes::Moves::Orthogonal.pairs(&grid),
+            clues: vec![(shapes::Cell(0, 0), 1), (shapes::Cell(2, 1), 2)],
+        }];
+        let mut state = states::State {
+            tokensets: vec![states::shadeset(&[
+                "o..".to_string(),
+                "..o".to_string(),
+                "...".to_string(),
+            ])],
+        };
+        apply_constraints(&mut state, &constraints);
+        let expected = states::shadeset(&["o#.".to_string(), "#.o".to_string(), "...".to_string()]);
+        assert_eq!(state.tokensets[0], expected);
+    }
 }
@@ -1,8 +1,12 @@
 mod apply_constraints;
+mod region_permutations;
+mod seen_cells;
 mod solution_count;
 pub mod strategies;
 mod symbol_positions;
 
 pub use apply_constraints::apply_constraints;
+use region_permutations::region_permutations;
+use seen_cells::seen_cells;
 pub use solution_count::count_solutions;
 use symbol_positions::symbol_positions;
@@ -0,0 +1,69 @@
+use crate::settings;
+use crate::shapes;
+use crate::states;
+
+pub fn region_permutations(
+    #[allow(clippy::ptr_arg)] cells: &states::CellGrid,
+    region: &shapes::Region,
+    all_symbols: &[settings::SymbolType],
+) -> Vec<Vec<settings::SymbolType>> {
+    let possibilities = region
+        .cells
+        .iter()
+        .map(|cell| match &cells[cell] {
+            states::CellState::Set(value) => vec![*value],
+            states::CellState::Candidates(candidates) => candidates.chars().collect(),
+            states::CellState::Empty => all_symbols.to_vec(),
+        })
+        .collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut current = Vec::new();
+    extend_permutations(&possibilities, &mut current, &mut result);
+    result
+}
+
+fn extend_permutations(
+    possibilities: &[Vec<settings::SymbolType>],
+    current: &mut Vec<settings::SymbolType>,
+    result: &mut Vec<Vec<settings::SymbolType>>,
+) {
+    let symbols = match possibilities.get(current.len()) {
+        Some(symbols) => symbols,
+        None => {
+            result.push(current.clone());
+            return;
+        }
+    };
+    for symbol in symbols {
+        if current.contains(symbol) {
+            continue;
+        }
+        current.push(*symbol);
+        extend_permutations(possibilities, current, result);
+        current.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutations() {
+        let cells = vec![vec![
+            states::CellState::Set('1'),
+            states::CellState::Candidates("12".to_string()),
+            states::CellState::Empty,
+        ]];
+        let region = shapes::Region {
+            cells: (0..3).map(|x| shapes::Cell(x, 0)).collect(),
+        };
+
+        let result = region_permutations(&cells, &region, &['1', '2', '3']);
+        let expected = vec![vec!['1', '2', '3']];
+        assert_eq!(result, expected);
+
+        let result = region_permutations(&cells, &region, &['1', '2']);
+        assert!(result.is_empty());
+    }
+}
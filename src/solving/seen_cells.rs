@@ -0,0 +1,93 @@
+use crate::settings;
+use crate::shapes;
+
+type SeenCells = std::collections::HashMap<shapes::Cell, std::collections::HashSet<shapes::Cell>>;
+
+pub fn seen_cells(
+    constraints: &[settings::Constraint],
+    tokenset_index: settings::TokenSetIndex,
+) -> SeenCells {
+    let mut result = SeenCells::new();
+    let mut add = |first: &shapes::Cell, second: &shapes::Cell| {
+        result
+            .entry(first.clone())
+            .or_default()
+            .insert(second.clone());
+        result
+            .entry(second.clone())
+            .or_default()
+            .insert(first.clone());
+    };
+    for constraint in constraints {
+        match constraint {
+            settings::Constraint::SymbolRepartition {
+                tokenset,
+                repartition,
+                region,
+            } if *tokenset == tokenset_index && repartition.values().all(|count| *count <= 1) => {
+                for (index, first) in region.cells.iter().enumerate() {
+                    for second in &region.cells[index + 1..] {
+                        add(first, second);
+                    }
+                }
+            }
+            settings::Constraint::Different {
+                tokenset,
+                first,
+                second,
+            } if *tokenset == tokenset_index => add(first, second),
+            _ => (),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nominal() {
+        let constraints = vec![
+            settings::Constraint::SymbolRepartition {
+                tokenset: 0,
+                repartition: "12".chars().map(|symbol| (symbol, 1)).collect(),
+                region: shapes::Region {
+                    cells: vec![shapes::Cell(0, 0), shapes::Cell(1, 0)],
+                },
+            },
+            settings::Constraint::Different {
+                tokenset: 0,
+                first: shapes::Cell(0, 0),
+                second: shapes::Cell(2, 1),
+            },
+            settings::Constraint::Different {
+                tokenset: 1,
+                first: shapes::Cell(0, 0),
+                second: shapes::Cell(0, 1),
+            },
+        ];
+        let result = seen_cells(&constraints, 0);
+        let expected = [
+            (
+                shapes::Cell(0, 0),
+                [shapes::Cell(1, 0), shapes::Cell(2, 1)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            (
+                shapes::Cell(1, 0),
+                [shapes::Cell(0, 0)].iter().cloned().collect(),
+            ),
+            (
+                shapes::Cell(2, 1),
+                [shapes::Cell(0, 0)].iter().cloned().collect(),
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(result, expected);
+    }
+}
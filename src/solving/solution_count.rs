@@ -8,6 +8,14 @@ enum Iterator {
         cell: shapes::Cell,
         candidates: String,
     },
+    Shadeset {
+        tokenset: usize,
+        cell: shapes::Cell,
+    },
+    Edgeset {
+        tokenset: usize,
+        edge: shapes::Edge,
+    },
     Unset,
 }
 
@@ -15,7 +23,7 @@ pub fn count_solutions(
     setting: &settings::PuzzleSetting,
     state: &mut states::State,
 ) -> Result<usize, Box<dyn std::error::Error>> {
-    let constraints = settings::list_constraints(&setting)?;
+    let constraints = settings::list_constraints(setting)?;
     do_count_solutions(setting, state, &constraints)
 }
 
@@ -30,33 +38,91 @@ fn do_count_solutions(
         states::Status::Invalid => return Ok(0),
         states::Status::Ongoing => (),
     }
-    let iterator = find_pivot(&setting, &state);
+    let iterator = find_pivot(setting, state);
     match iterator {
         Iterator::Symbolset {
             tokenset,
             cell,
             candidates,
         } => {
-            let previous = {
-                let tokenset = &mut state.tokensets[tokenset];
-                let states::Tokenset::Symbols(cells) = tokenset;
-                cells[&cell].clone()
-            };
+            let linked = std::iter::once((tokenset, cell.clone()))
+                .chain(linked_cells(constraints, tokenset, &cell))
+                .filter_map(|(tokenset, cell)| match &state.tokensets[tokenset] {
+                    states::Tokenset::Symbols(cells) => {
+                        let previous = cells[&cell].clone();
+                        Some((tokenset, cell, previous))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
             for candidate in candidates.chars() {
-                let tokenset = &mut state.tokensets[tokenset];
-                let states::Tokenset::Symbols(cells) = tokenset;
-                cells[&cell] = states::CellState::Set(candidate);
+                for (tokenset, cell, _) in &linked {
+                    if let states::Tokenset::Symbols(cells) = &mut state.tokensets[*tokenset] {
+                        cells[cell] = states::CellState::Set(candidate);
+                    }
+                }
                 result += do_count_solutions(setting, state, constraints)?;
             }
-            let tokenset = &mut state.tokensets[tokenset];
-            let states::Tokenset::Symbols(cells) = tokenset;
-            cells[&cell] = previous;
+            for (tokenset, cell, previous) in linked {
+                if let states::Tokenset::Symbols(cells) = &mut state.tokensets[tokenset] {
+                    cells[&cell] = previous;
+                }
+            }
+        }
+        Iterator::Shadeset { tokenset, cell } => {
+            for shade in [states::ShadeState::Shaded, states::ShadeState::Unshaded] {
+                if let states::Tokenset::Shading(cells) = &mut state.tokensets[tokenset] {
+                    cells[&cell] = shade;
+                }
+                result += do_count_solutions(setting, state, constraints)?;
+            }
+            if let states::Tokenset::Shading(cells) = &mut state.tokensets[tokenset] {
+                cells[&cell] = states::ShadeState::Unknown;
+            }
+        }
+        Iterator::Edgeset { tokenset, edge } => {
+            for edge_state in [states::EdgeState::On, states::EdgeState::Off] {
+                if let states::Tokenset::Edges(cells) = &mut state.tokensets[tokenset] {
+                    cells[&edge] = edge_state;
+                }
+                result += do_count_solutions(setting, state, constraints)?;
+            }
+            if let states::Tokenset::Edges(cells) = &mut state.tokensets[tokenset] {
+                cells[&edge] = states::EdgeState::Unknown;
+            }
         }
         Iterator::Unset => (),
     }
     Ok(result)
 }
 
+fn linked_cells(
+    constraints: &[settings::Constraint],
+    tokenset_index: usize,
+    target: &shapes::Cell,
+) -> Vec<(usize, shapes::Cell)> {
+    constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            settings::Constraint::SharedCell {
+                tokenset,
+                cell,
+                other_tokenset,
+                other_cell,
+            } => {
+                if *tokenset == tokenset_index && cell == target {
+                    Some((*other_tokenset, other_cell.clone()))
+                } else if *other_tokenset == tokenset_index && other_cell == target {
+                    Some((*tokenset, cell.clone()))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 fn find_pivot(setting: &settings::PuzzleSetting, state: &states::State) -> Iterator {
     for (index, tokenset) in state.tokensets.iter().enumerate() {
         match tokenset {
@@ -68,12 +134,15 @@ fn find_pivot(setting: &settings::PuzzleSetting, state: &states::State) -> Itera
                             continue;
                         }
                         let setting_tokenset = &setting.tokensets[index];
-                        let candidates = match setting_tokenset {
+                        let (grid, candidates) = match setting_tokenset {
                             settings::TokenSet::Symbols {
-                                grid: _,
-                                candidates,
-                            } => candidates.clone(),
+                                grid, candidates, ..
+                            } => (&setting.grids[*grid], candidates.clone()),
+                            _ => continue,
                         };
+                        if !grid.contains(&cell) {
+                            continue;
+                        }
                         return Iterator::Symbolset {
                             tokenset: index,
                             cell,
@@ -82,6 +151,40 @@ fn find_pivot(setting: &settings::PuzzleSetting, state: &states::State) -> Itera
                     }
                 }
             }
+            states::Tokenset::Shading(cells) => {
+                let grid = match &setting.tokensets[index] {
+                    settings::TokenSet::Shading { grid } => &setting.grids[*grid],
+                    _ => continue,
+                };
+                for row in 0..cells.len() {
+                    for column in 0..cells[row].len() {
+                        let cell = shapes::Cell(column, row);
+                        if cells[&cell] == states::ShadeState::Unknown && grid.contains(&cell) {
+                            return Iterator::Shadeset {
+                                tokenset: index,
+                                cell,
+                            };
+                        }
+                    }
+                }
+            }
+            states::Tokenset::Edges(cells) => {
+                let (grid, lattice) = match &setting.tokensets[index] {
+                    settings::TokenSet::Edges { grid, lattice } => {
+                        (&setting.grids[*grid], *lattice)
+                    }
+                    _ => continue,
+                };
+                let edge = shapes::Edge::all(grid, lattice)
+                    .into_iter()
+                    .find(|edge| cells[edge] == states::EdgeState::Unknown);
+                if let Some(edge) = edge {
+                    return Iterator::Edgeset {
+                        tokenset: index,
+                        edge,
+                    };
+                }
+            }
         }
     }
     Iterator::Unset
@@ -90,6 +193,7 @@ fn find_pivot(setting: &settings::PuzzleSetting, state: &states::State) -> Itera
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::settings::presets;
     use crate::settings::presets::sudoku;
     use crate::states;
 
@@ -101,7 +205,7 @@ mod tests {
             ".1..".to_string(),
             "...4".to_string(),
         ];
-        let setting = sudoku::setting(2, &grid);
+        let setting = sudoku::setting(2, &grid)?;
         let mut state = states::State {
             tokensets: vec![states::symbolset(
                 &grid,
@@ -121,7 +225,7 @@ mod tests {
             ".1..".to_string(),
             "...4".to_string(),
         ];
-        let setting = sudoku::setting(2, &grid);
+        let setting = sudoku::setting(2, &grid)?;
         let mut state = states::State {
             tokensets: vec![states::symbolset(&grid, states::CellState::Empty)],
         };
@@ -131,4 +235,116 @@ mod tests {
         assert_eq!(state, starting_state);
         Ok(())
     }
+
+    #[test]
+    fn test_masked_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let missing = std::iter::once(shapes::Cell(1, 1)).collect();
+        let grid = setting.add_masked_grid(3, 3, missing);
+        let givens = vec![settings::GivenSymbol {
+            cell: shapes::Cell(0, 0),
+            symbol: '1',
+        }];
+        sudoku::add_symbolset(&mut setting, grid, 3, Vec::new(), givens)?;
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        let result = count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_masked_box() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let missing = std::iter::once(shapes::Cell(0, 0)).collect();
+        let grid = setting.add_masked_grid(4, 4, missing);
+        let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
+        let givens = presets::givens(&[
+            ".1..".to_string(),
+            "..3.".to_string(),
+            ".4..".to_string(),
+            "...2".to_string(),
+        ]);
+        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens)?;
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        let result = count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        setting.grids.push(settings::Grid::hexagon(1));
+        sudoku::add_symbolset(&mut setting, 0, 3, Vec::new(), Vec::new())?;
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        let result = count_solutions(&setting, &mut state)?;
+        assert_eq!(result, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shading() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(1, 3);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Shading { grid });
+        setting.rules.push(settings::Rule::Shading {
+            tokenset,
+            givens: vec![settings::GivenShade {
+                shaded: true,
+                cell: shapes::Cell(0, 0),
+            }],
+        });
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        let starting_state = state.clone();
+        assert_eq!(count_solutions(&setting, &mut state)?, 4);
+        assert_eq!(state, starting_state);
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_loop() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(2, 3);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Edges {
+            grid,
+            lattice: shapes::Lattice::Centers,
+        });
+        setting.rules.push(settings::Rule::SingleLoop { tokenset });
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        assert_eq!(count_solutions(&setting, &mut state)?, 3);
+
+        setting.rules.push(settings::Rule::Edges {
+            tokenset,
+            givens: vec![settings::GivenEdge {
+                on: true,
+                edge: shapes::Edge::Horizontal(0, 0),
+            }],
+        });
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        assert_eq!(count_solutions(&setting, &mut state)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nurikabe() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Shading { grid });
+        setting.rules.push(settings::Rule::Nurikabe {
+            tokenset,
+            clues: vec![
+                settings::IslandClue {
+                    size: 3,
+                    cell: shapes::Cell(1, 1),
+                },
+                settings::IslandClue {
+                    size: 1,
+                    cell: shapes::Cell(3, 3),
+                },
+            ],
+        });
+        let mut state = states::initialize(&setting, states::Purpose::Solving)?;
+        assert_eq!(count_solutions(&setting, &mut state)?, 1);
+        Ok(())
+    }
 }
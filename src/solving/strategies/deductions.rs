@@ -8,6 +8,18 @@ pub enum Category {
         region: shapes::Region,
         positions: Vec<shapes::Cell>,
     },
+    LockedCandidates {
+        symbol: settings::SymbolType,
+        region: shapes::Region,
+        positions: Vec<shapes::Cell>,
+    },
+    Sandwich {
+        region: shapes::Region,
+        sum: usize,
+    },
+    LinePermutations {
+        region: shapes::Region,
+    },
 }
 
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize)]
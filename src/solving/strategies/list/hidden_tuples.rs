@@ -24,7 +24,9 @@ impl strategies::Strategy for HiddenTuples {
                 region,
             } = constraint
             {
-                let states::Tokenset::Symbols(cells) = &state.tokensets[*tokenset];
+                let states::Tokenset::Symbols(cells) = &state.tokensets[*tokenset] else {
+                    continue;
+                };
                 result.extend(self.search_for_constraint(*tokenset, repartition, region, cells)?);
             }
         }
@@ -192,16 +194,20 @@ mod tests {
         ];
         assert_eq!(deduction.actions, expected_actions);
 
-        let strategies::Category::HiddenTuple {
-            symbols,
-            region: found_region,
-            positions,
-        } = &deduction.category;
-        let mut positions = positions.clone();
-        positions.sort();
-        assert_eq!(positions, vec![shapes::Cell(0, 0), shapes::Cell(1, 0)]);
-        assert_eq!(*found_region, region);
-        assert_eq!(*symbols.chars().sorted().join(""), "34".to_string());
+        match &deduction.category {
+            strategies::Category::HiddenTuple {
+                symbols,
+                region: found_region,
+                positions,
+            } => {
+                let mut positions = positions.clone();
+                positions.sort();
+                assert_eq!(positions, vec![shapes::Cell(0, 0), shapes::Cell(1, 0)]);
+                assert_eq!(*found_region, region);
+                assert_eq!(*symbols.chars().sorted().join(""), "34".to_string());
+            }
+            category => panic!("unexpected category {:?}", category),
+        }
 
         Ok(())
     }
@@ -0,0 +1,301 @@
+use crate::settings;
+use crate::shapes;
+use crate::solving;
+use crate::solving::strategies;
+use crate::states;
+
+pub struct LinePermutations {}
+
+struct Group<'a> {
+    tokenset: settings::TokenSetIndex,
+    region: &'a shapes::Region,
+    constraints: Vec<&'a settings::Constraint>,
+}
+
+impl strategies::Strategy for LinePermutations {
+    fn search(
+        &self,
+        constraints: &[settings::Constraint],
+        state: &states::State,
+    ) -> Result<Vec<strategies::Deduction>, Box<dyn std::error::Error>> {
+        let mut result = Vec::new();
+        for group in group_constraints(constraints) {
+            let states::Tokenset::Symbols(cells) = &state.tokensets[group.tokenset] else {
+                continue;
+            };
+            // Only regions holding distinct symbols are searched, as permutations.
+            if !is_distinct(constraints, group.tokenset, group.region) {
+                continue;
+            }
+            let actions = search_for_group(&group, cells)?;
+            if !actions.is_empty() {
+                result.push(strategies::Deduction {
+                    category: strategies::Category::LinePermutations {
+                        region: group.region.clone(),
+                    },
+                    actions,
+                });
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn sorted_cells(region: &shapes::Region) -> Vec<shapes::Cell> {
+    let mut result = region.cells.clone();
+    result.sort();
+    result
+}
+
+fn group_constraints(constraints: &[settings::Constraint]) -> Vec<Group<'_>> {
+    let mut result: Vec<Group> = Vec::new();
+    for constraint in constraints {
+        let (tokenset, region) = match constraint {
+            settings::Constraint::Visibility {
+                tokenset, region, ..
+            }
+            | settings::Constraint::XSum {
+                tokenset, region, ..
+            }
+            | settings::Constraint::Sandwich {
+                tokenset, region, ..
+            } => (*tokenset, region),
+            _ => continue,
+        };
+        let key = sorted_cells(region);
+        match result
+            .iter_mut()
+            .find(|group| group.tokenset == tokenset && sorted_cells(group.region) == key)
+        {
+            Some(group) => group.constraints.push(constraint),
+            None => result.push(Group {
+                tokenset,
+                region,
+                constraints: vec![constraint],
+            }),
+        }
+    }
+    result
+}
+
+fn is_distinct(
+    constraints: &[settings::Constraint],
+    tokenset_index: settings::TokenSetIndex,
+    region: &shapes::Region,
+) -> bool {
+    let key = sorted_cells(region);
+    constraints.iter().any(|constraint| match constraint {
+        settings::Constraint::SymbolRepartition {
+            tokenset,
+            repartition,
+            region,
+        } => {
+            *tokenset == tokenset_index
+                && repartition.values().all(|count| *count <= 1)
+                && sorted_cells(region) == key
+        }
+        _ => false,
+    })
+}
+
+fn constraint_symbols(constraint: &settings::Constraint) -> Vec<settings::SymbolType> {
+    match constraint {
+        settings::Constraint::Visibility { order, .. } => order.keys().cloned().collect(),
+        settings::Constraint::XSum { values, .. }
+        | settings::Constraint::Sandwich { values, .. } => values.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn accepts(
+    constraint: &settings::Constraint,
+    assignment: &std::collections::HashMap<&shapes::Cell, settings::SymbolType>,
+) -> bool {
+    let symbols = |region: &shapes::Region| {
+        region
+            .cells
+            .iter()
+            .map(|cell| assignment[cell])
+            .collect::<Vec<_>>()
+    };
+    match constraint {
+        settings::Constraint::Visibility {
+            region,
+            order,
+            count,
+            ..
+        } => settings::visible_count(&symbols(region), order) == *count,
+        settings::Constraint::XSum {
+            region,
+            values,
+            sum,
+            ..
+        } => settings::x_sum(&symbols(region), values) == Some(*sum),
+        settings::Constraint::Sandwich {
+            region,
+            crusts,
+            values,
+            sum,
+            ..
+        } => settings::sandwich_sum(&symbols(region), *crusts, values) == Some(*sum),
+        _ => true,
+    }
+}
+
+fn search_for_group(
+    group: &Group,
+    #[allow(clippy::ptr_arg)] cells: &states::CellGrid,
+) -> Result<Vec<strategies::Action>, Box<dyn std::error::Error>> {
+    let mut all_symbols = group
+        .constraints
+        .iter()
+        .flat_map(|constraint| constraint_symbols(constraint))
+        .collect::<Vec<_>>();
+    all_symbols.sort();
+    all_symbols.dedup();
+    let mut allowed = vec![std::collections::HashSet::new(); group.region.cells.len()];
+    for permutation in solving::region_permutations(cells, group.region, &all_symbols) {
+        let assignment = group
+            .region
+            .cells
+            .iter()
+            .zip(permutation.iter().cloned())
+            .collect();
+        if group
+            .constraints
+            .iter()
+            .all(|constraint| accepts(constraint, &assignment))
+        {
+            for (symbols, symbol) in allowed.iter_mut().zip(permutation) {
+                symbols.insert(symbol);
+            }
+        }
+    }
+    if allowed.iter().any(|symbols| symbols.is_empty()) {
+        return Err("No valid permutation".to_string().into());
+    }
+    let mut result = Vec::new();
+    for (cell, symbols) in group.region.cells.iter().zip(allowed) {
+        if let states::CellState::Candidates(candidates) = &cells[cell] {
+            for candidate in candidates.chars() {
+                if !symbols.contains(&candidate) {
+                    result.push(strategies::Action::RemoveSymbolCandidate {
+                        tokenset: group.tokenset,
+                        cell: cell.clone(),
+                        symbol: candidate,
+                    });
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving::strategies::Strategy;
+
+    #[test]
+    fn test_both_ends() -> Result<(), Box<dyn std::error::Error>> {
+        let state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+            ]])],
+        };
+        let region = shapes::Region {
+            cells: (0..4).map(|x| shapes::Cell(x, 0)).collect(),
+        };
+        let mut reversed = region.clone();
+        reversed.cells.reverse();
+        let order: settings::SymbolOrder = "1234"
+            .chars()
+            .enumerate()
+            .map(|(rank, symbol)| (symbol, rank))
+            .collect();
+        let constraints = vec![
+            settings::Constraint::SymbolRepartition {
+                tokenset: 0,
+                repartition: "1234".chars().map(|symbol| (symbol, 1)).collect(),
+                region: region.clone(),
+            },
+            settings::Constraint::Visibility {
+                tokenset: 0,
+                region: region.clone(),
+                order: order.clone(),
+                count: 3,
+            },
+            settings::Constraint::Visibility {
+                tokenset: 0,
+                region: reversed,
+                order,
+                count: 2,
+            },
+        ];
+
+        let technique = LinePermutations {};
+        let mut result = technique.search(&constraints, &state)?;
+        assert_eq!(result.len(), 1);
+        let deduction = &mut result[0];
+        deduction.actions.sort();
+        let remaining =
+            deduction
+                .actions
+                .iter()
+                .fold(vec!["1234".to_string(); 4], |mut remaining, action| {
+                    if let strategies::Action::RemoveSymbolCandidate { cell, symbol, .. } = action {
+                        remaining[cell.0].retain(|candidate| candidate != *symbol);
+                    }
+                    remaining
+                });
+        assert_eq!(remaining, vec!["12", "23", "4", "123"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_x_sum() -> Result<(), Box<dyn std::error::Error>> {
+        let state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+            ]])],
+        };
+        let region = shapes::Region {
+            cells: (0..4).map(|x| shapes::Cell(x, 0)).collect(),
+        };
+        let constraints = vec![
+            settings::Constraint::SymbolRepartition {
+                tokenset: 0,
+                repartition: "1234".chars().map(|symbol| (symbol, 1)).collect(),
+                region: region.clone(),
+            },
+            settings::Constraint::XSum {
+                tokenset: 0,
+                region,
+                values: settings::digit_values("1234"),
+                sum: 10,
+            },
+        ];
+
+        let technique = LinePermutations {};
+        let mut result = technique.search(&constraints, &state)?;
+        assert_eq!(result.len(), 1);
+        let deduction = &mut result[0];
+        deduction.actions.sort();
+        let expected = [(0, '1'), (0, '2'), (0, '3'), (1, '4'), (2, '4'), (3, '4')]
+            .iter()
+            .map(|(x, symbol)| strategies::Action::RemoveSymbolCandidate {
+                tokenset: 0,
+                cell: shapes::Cell(*x, 0),
+                symbol: *symbol,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(deduction.actions, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use crate::settings;
+use crate::shapes;
+use crate::solving;
+use crate::solving::strategies;
+use crate::states;
+
+pub struct LockedCandidates {}
+
+impl strategies::Strategy for LockedCandidates {
+    fn search(
+        &self,
+        constraints: &[settings::Constraint],
+        state: &states::State,
+    ) -> Result<Vec<strategies::Deduction>, Box<dyn std::error::Error>> {
+        let mut result = Vec::new();
+        let mut seen_cells = std::collections::HashMap::new();
+        for constraint in constraints {
+            if let settings::Constraint::SymbolRepartition {
+                tokenset,
+                repartition,
+                region,
+            } = constraint
+            {
+                let states::Tokenset::Symbols(cells) = &state.tokensets[*tokenset] else {
+                    continue;
+                };
+                let seen = seen_cells
+                    .entry(*tokenset)
+                    .or_insert_with(|| solving::seen_cells(constraints, *tokenset));
+                let (set_positions, candidate_positions) = solving::symbol_positions(cells, region);
+                for (symbol, count) in repartition {
+                    if *count != 1 || set_positions.contains_key(symbol) {
+                        continue;
+                    }
+                    let positions = match candidate_positions.get(symbol) {
+                        Some(positions) => positions,
+                        None => continue,
+                    };
+                    let actions = list_actions(*tokenset, cells, seen, *symbol, positions);
+                    if !actions.is_empty() {
+                        let mut positions = positions.iter().cloned().collect::<Vec<_>>();
+                        positions.sort();
+                        result.push(strategies::Deduction {
+                            category: strategies::Category::LockedCandidates {
+                                symbol: *symbol,
+                                region: region.clone(),
+                                positions,
+                            },
+                            actions,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn list_actions(
+    tokenset: settings::TokenSetIndex,
+    #[allow(clippy::ptr_arg)] cells: &states::CellGrid,
+    seen: &std::collections::HashMap<shapes::Cell, std::collections::HashSet<shapes::Cell>>,
+    symbol: settings::SymbolType,
+    positions: &std::collections::HashSet<shapes::Cell>,
+) -> Vec<strategies::Action> {
+    let mut targets: Option<std::collections::HashSet<shapes::Cell>> = None;
+    for position in positions {
+        let seen_by_position = match seen.get(position) {
+            Some(seen_by_position) => seen_by_position,
+            None => return Vec::new(),
+        };
+        targets = Some(match targets {
+            Some(targets) => targets.intersection(seen_by_position).cloned().collect(),
+            None => seen_by_position.clone(),
+        });
+    }
+    let mut result = targets
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|cell| !positions.contains(cell))
+        .filter(|cell| match &cells[cell] {
+            states::CellState::Candidates(candidates) => candidates.contains(symbol),
+            _ => false,
+        })
+        .map(|cell| strategies::Action::RemoveSymbolCandidate {
+            tokenset,
+            cell,
+            symbol,
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving::strategies::Strategy;
+
+    #[test]
+    fn test_pointing_through_different_pairs() -> Result<(), Box<dyn std::error::Error>> {
+        let state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![
+                vec![
+                    states::CellState::Candidates("12".to_string()),
+                    states::CellState::Candidates("12".to_string()),
+                    states::CellState::Set('3'),
+                ],
+                vec![
+                    states::CellState::Candidates("123".to_string()),
+                    states::CellState::Candidates("123".to_string()),
+                    states::CellState::Candidates("123".to_string()),
+                ],
+            ])],
+        };
+        let region = shapes::Region {
+            cells: (0..3).map(|x| shapes::Cell(x, 0)).collect(),
+        };
+        let constraints = vec![
+            settings::Constraint::SymbolRepartition {
+                tokenset: 0,
+                repartition: "123".chars().map(|symbol| (symbol, 1)).collect(),
+                region: region.clone(),
+            },
+            settings::Constraint::Different {
+                tokenset: 0,
+                first: shapes::Cell(0, 0),
+                second: shapes::Cell(2, 1),
+            },
+            settings::Constraint::Different {
+                tokenset: 0,
+                first: shapes::Cell(1, 0),
+                second: shapes::Cell(2, 1),
+            },
+        ];
+
+        let technique = LockedCandidates {};
+        let mut result = technique.search(&constraints, &state)?;
+        result.sort_by(|first, second| first.actions.cmp(&second.actions));
+        let expected = ['1', '2']
+            .iter()
+            .map(|symbol| strategies::Deduction {
+                category: strategies::Category::LockedCandidates {
+                    symbol: *symbol,
+                    region: region.clone(),
+                    positions: vec![shapes::Cell(0, 0), shapes::Cell(1, 0)],
+                },
+                actions: vec![strategies::Action::RemoveSymbolCandidate {
+                    tokenset: 0,
+                    cell: shapes::Cell(2, 1),
+                    symbol: *symbol,
+                }],
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+        Ok(())
+    }
+}
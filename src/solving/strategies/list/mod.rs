@@ -1,3 +1,9 @@
 mod hidden_tuples;
+mod line_permutations;
+mod locked_candidates;
+mod sandwiches;
 
 pub use hidden_tuples::HiddenTuples;
+pub use line_permutations::LinePermutations;
+pub use locked_candidates::LockedCandidates;
+pub use sandwiches::Sandwiches;
@@ -0,0 +1,215 @@
+use crate::settings;
+use crate::shapes;
+use crate::solving::strategies;
+use crate::states;
+
+pub struct Sandwiches {}
+
+impl strategies::Strategy for Sandwiches {
+    fn search(
+        &self,
+        constraints: &[settings::Constraint],
+        state: &states::State,
+    ) -> Result<Vec<strategies::Deduction>, Box<dyn std::error::Error>> {
+        let mut result = Vec::new();
+        for constraint in constraints {
+            if let settings::Constraint::Sandwich {
+                tokenset,
+                region,
+                crusts,
+                values,
+                sum,
+            } = constraint
+            {
+                let states::Tokenset::Symbols(cells) = &state.tokensets[*tokenset] else {
+                    continue;
+                };
+                let actions =
+                    search_for_constraint(*tokenset, region, *crusts, values, *sum, cells)?;
+                if !actions.is_empty() {
+                    result.push(strategies::Deduction {
+                        category: strategies::Category::Sandwich {
+                            region: region.clone(),
+                            sum: *sum,
+                        },
+                        actions,
+                    });
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn search_for_constraint(
+    tokenset: settings::TokenSetIndex,
+    region: &shapes::Region,
+    crusts: (settings::SymbolType, settings::SymbolType),
+    values: &settings::SymbolValues,
+    sum: usize,
+    #[allow(clippy::ptr_arg)] cells: &states::CellGrid,
+) -> Result<Vec<strategies::Action>, Box<dyn std::error::Error>> {
+    let states = region
+        .cells
+        .iter()
+        .map(|cell| &cells[cell])
+        .collect::<Vec<_>>();
+    let mut feasible = vec![(false, false); states.len()];
+    for first in 0..states.len() {
+        for second in 0..states.len() {
+            if first != second && is_feasible(&states, (first, second), crusts, values, sum) {
+                feasible[first].0 = true;
+                feasible[second].1 = true;
+            }
+        }
+    }
+    if feasible.iter().all(|(first, second)| !first && !second) {
+        return Err("No valid sandwich placement".to_string().into());
+    }
+    let mut result = Vec::new();
+    for (cell, (cell_state, (first, second))) in
+        region.cells.iter().zip(states.iter().zip(feasible))
+    {
+        if let states::CellState::Candidates(candidates) = cell_state {
+            for (symbol, possible) in [(crusts.0, first), (crusts.1, second)].iter() {
+                if !possible && candidates.contains(*symbol) {
+                    result.push(strategies::Action::RemoveSymbolCandidate {
+                        tokenset,
+                        cell: cell.clone(),
+                        symbol: *symbol,
+                    });
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn can_hold(cell_state: &states::CellState, symbol: settings::SymbolType) -> bool {
+    match cell_state {
+        states::CellState::Set(value) => *value == symbol,
+        states::CellState::Candidates(candidates) => candidates.contains(symbol),
+        states::CellState::Empty => true,
+    }
+}
+
+fn is_feasible(
+    states: &[&states::CellState],
+    positions: (usize, usize),
+    crusts: (settings::SymbolType, settings::SymbolType),
+    values: &settings::SymbolValues,
+    sum: usize,
+) -> bool {
+    if !can_hold(states[positions.0], crusts.0) || !can_hold(states[positions.1], crusts.1) {
+        return false;
+    }
+    let misplaced_crust = states.iter().enumerate().any(|(index, cell_state)| {
+        index != positions.0
+            && index != positions.1
+            && (**cell_state == states::CellState::Set(crusts.0)
+                || **cell_state == states::CellState::Set(crusts.1))
+    });
+    if misplaced_crust {
+        return false;
+    }
+    let (start, end) = if positions.0 < positions.1 {
+        positions
+    } else {
+        (positions.1, positions.0)
+    };
+    let mut min_sum = 0;
+    let mut max_sum = 0;
+    for cell_state in &states[start + 1..end] {
+        let possible_values = values
+            .iter()
+            .filter(|(symbol, _)| {
+                **symbol != crusts.0 && **symbol != crusts.1 && can_hold(cell_state, **symbol)
+            })
+            .map(|(_, value)| *value)
+            .collect::<Vec<_>>();
+        match (possible_values.iter().min(), possible_values.iter().max()) {
+            (Some(min), Some(max)) => {
+                min_sum += min;
+                max_sum += max;
+            }
+            _ => return false,
+        }
+    }
+    min_sum <= sum && sum <= max_sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solving::strategies::Strategy;
+
+    fn sample_constraints(sum: usize) -> Vec<settings::Constraint> {
+        vec![settings::Constraint::Sandwich {
+            tokenset: 0,
+            region: shapes::Region {
+                cells: (0..4).map(|x| shapes::Cell(x, 0)).collect(),
+            },
+            crusts: ('1', '4'),
+            values: settings::digit_values("1234"),
+            sum,
+        }]
+    }
+
+    #[test]
+    fn test_remove_crusts() -> Result<(), Box<dyn std::error::Error>> {
+        let state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+                states::CellState::Candidates("1234".to_string()),
+            ]])],
+        };
+
+        let technique = Sandwiches {};
+        let mut result = technique.search(&sample_constraints(5), &state)?;
+        assert_eq!(result.len(), 1);
+        let deduction = &mut result[0];
+        deduction.actions.sort();
+        let expected_actions = vec![
+            strategies::Action::RemoveSymbolCandidate {
+                tokenset: 0,
+                cell: shapes::Cell(1, 0),
+                symbol: '1',
+            },
+            strategies::Action::RemoveSymbolCandidate {
+                tokenset: 0,
+                cell: shapes::Cell(1, 0),
+                symbol: '4',
+            },
+            strategies::Action::RemoveSymbolCandidate {
+                tokenset: 0,
+                cell: shapes::Cell(2, 0),
+                symbol: '1',
+            },
+            strategies::Action::RemoveSymbolCandidate {
+                tokenset: 0,
+                cell: shapes::Cell(2, 0),
+                symbol: '4',
+            },
+        ];
+        assert_eq!(deduction.actions, expected_actions);
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_placement() {
+        let state = states::State {
+            tokensets: vec![states::Tokenset::Symbols(vec![vec![
+                states::CellState::Set('1'),
+                states::CellState::Set('4'),
+                states::CellState::Candidates("23".to_string()),
+                states::CellState::Candidates("23".to_string()),
+            ]])],
+        };
+
+        let technique = Sandwiches {};
+        let result = technique.search(&sample_constraints(2), &state);
+        assert!(result.is_err());
+    }
+}
@@ -15,6 +15,9 @@ impl Default for Solver {
                 Box::new(list::HiddenTuples::with_fixed_size(2)),
                 Box::new(list::HiddenTuples::with_fixed_size(3)),
                 Box::new(list::HiddenTuples::with_min_size(4)),
+                Box::new(list::LockedCandidates {}),
+                Box::new(list::Sandwiches {}),
+                Box::new(list::LinePermutations {}),
             ],
         }
     }
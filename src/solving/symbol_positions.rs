@@ -16,13 +16,13 @@ pub fn symbol_positions(
             states::CellState::Set(value) => {
                 set_positions
                     .entry(*value)
-                    .or_insert_with(std::collections::HashSet::new)
+                    .or_default()
                     .insert(cell.clone());
             }
             states::CellState::Candidates(candidates) => candidates.chars().for_each(|candidate| {
                 candidate_positions
                     .entry(candidate)
-                    .or_insert_with(std::collections::HashSet::new)
+                    .or_default()
                     .insert(cell.clone());
             }),
             states::CellState::Empty => (),
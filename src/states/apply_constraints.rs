@@ -4,13 +4,35 @@ use crate::states;
 
 pub fn apply_constraints(state: &mut states::State, constraints: &[settings::Constraint]) {
     for constraint in constraints {
-        if let settings::Constraint::GivenSymbol {
-            tokenset,
-            cell,
-            symbol,
-        } = constraint
-        {
-            apply_given_symbol(state, *tokenset, cell, *symbol);
+        match constraint {
+            settings::Constraint::GivenSymbol {
+                tokenset,
+                cell,
+                symbol,
+            } => apply_given_symbol(state, *tokenset, cell, *symbol),
+            settings::Constraint::GivenShade {
+                tokenset,
+                cell,
+                shaded,
+            } => apply_given_shade(state, *tokenset, cell, *shaded),
+            settings::Constraint::GivenEdge { tokenset, edge, on } => {
+                apply_given_edge(state, *tokenset, edge, *on)
+            }
+            settings::Constraint::AllowedSymbols {
+                tokenset,
+                cell,
+                symbols,
+            } => apply_allowed_symbols(state, *tokenset, cell, symbols),
+            settings::Constraint::SharedCell {
+                tokenset,
+                cell,
+                other_tokenset,
+                other_cell,
+            } => {
+                apply_shared_cell(state, *tokenset, cell, *other_tokenset, other_cell);
+                apply_shared_cell(state, *other_tokenset, other_cell, *tokenset, cell);
+            }
+            _ => (),
         }
     }
 }
@@ -22,6 +44,78 @@ fn apply_given_symbol(
     symbol: settings::SymbolType,
 ) {
     let tokenset = &mut state.tokensets[tokenset];
-    let states::Tokenset::Symbols(cells) = tokenset;
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
     cells[cell] = states::CellState::Set(symbol);
 }
+
+fn apply_given_shade(
+    state: &mut states::State,
+    tokenset: usize,
+    cell: &shapes::Cell,
+    shaded: bool,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Shading(cells) = tokenset else {
+        return;
+    };
+    cells[cell] = if shaded {
+        states::ShadeState::Shaded
+    } else {
+        states::ShadeState::Unshaded
+    };
+}
+
+fn apply_given_edge(state: &mut states::State, tokenset: usize, edge: &shapes::Edge, on: bool) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Edges(cells) = tokenset else {
+        return;
+    };
+    cells[edge] = if on {
+        states::EdgeState::On
+    } else {
+        states::EdgeState::Off
+    };
+}
+
+fn apply_allowed_symbols(
+    state: &mut states::State,
+    tokenset: usize,
+    cell: &shapes::Cell,
+    symbols: &str,
+) {
+    let tokenset = &mut state.tokensets[tokenset];
+    let states::Tokenset::Symbols(cells) = tokenset else {
+        return;
+    };
+    if let states::CellState::Candidates(candidates) = &mut cells[cell] {
+        *candidates = candidates
+            .chars()
+            .filter(|candidate| symbols.contains(*candidate))
+            .collect();
+    }
+}
+
+fn apply_shared_cell(
+    state: &mut states::State,
+    source_tokenset: usize,
+    source: &shapes::Cell,
+    target_tokenset: usize,
+    target: &shapes::Cell,
+) {
+    let states::Tokenset::Symbols(cells) = &state.tokensets[source_tokenset] else {
+        return;
+    };
+    let symbol = match &cells[source] {
+        states::CellState::Set(symbol) => *symbol,
+        _ => return,
+    };
+    let states::Tokenset::Symbols(cells) = &mut state.tokensets[target_tokenset] else {
+        return;
+    };
+    if let states::CellState::Set(_) = &cells[target] {
+        return;
+    }
+    cells[target] = states::CellState::Set(symbol);
+}
@@ -1,4 +1,5 @@
 use crate::settings;
+use crate::shapes;
 use crate::states;
 
 pub enum Purpose {
@@ -14,10 +15,20 @@ pub fn initialize(
         .tokensets
         .iter()
         .map(|tokenset| match tokenset {
-            settings::TokenSet::Symbols { grid, candidates } => {
+            settings::TokenSet::Symbols {
+                grid, candidates, ..
+            } => {
                 let grid = &setting.grids[*grid];
                 initialize_symbolset(grid, candidates.to_string(), &purpose)
             }
+            settings::TokenSet::Shading { grid } => {
+                let grid = &setting.grids[*grid];
+                initialize_shadeset(grid)
+            }
+            settings::TokenSet::Edges { grid, lattice } => {
+                let grid = &setting.grids[*grid];
+                initialize_edgeset(grid, *lattice)
+            }
         })
         .collect();
     let mut result = states::State { tokensets };
@@ -35,12 +46,54 @@ fn initialize_symbolset(
         Purpose::Playing => states::CellState::Empty,
         Purpose::Solving => states::CellState::Candidates(candidates.chars().collect()),
     };
-    let candidates = (0..grid.columns)
-        .map(|_| (0..grid.rows).map(|_| default.clone()).collect())
+    let candidates = (0..grid.rows)
+        .map(|row| {
+            (0..grid.columns)
+                .map(|column| {
+                    if grid.contains(&shapes::Cell(column, row)) {
+                        default.clone()
+                    } else {
+                        states::CellState::Empty
+                    }
+                })
+                .collect()
+        })
         .collect();
     states::Tokenset::Symbols(candidates)
 }
 
+fn initialize_shadeset(grid: &settings::Grid) -> states::Tokenset {
+    // Missing cells are left unshaded so that they never block completion.
+    let cells = (0..grid.rows)
+        .map(|row| {
+            (0..grid.columns)
+                .map(|column| {
+                    if grid.contains(&shapes::Cell(column, row)) {
+                        states::ShadeState::Unknown
+                    } else {
+                        states::ShadeState::Unshaded
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    states::Tokenset::Shading(cells)
+}
+
+fn initialize_edgeset(grid: &settings::Grid, lattice: shapes::Lattice) -> states::Tokenset {
+    // Edges outside of the grid are crossed out from the start.
+    let (columns, rows) = lattice.size(grid);
+    let off = |columns: usize, rows: usize| vec![vec![states::EdgeState::Off; columns]; rows];
+    let mut cells = states::EdgeGrid {
+        horizontal: off(columns.saturating_sub(1), rows),
+        vertical: off(columns, rows.saturating_sub(1)),
+    };
+    for edge in shapes::Edge::all(grid, lattice) {
+        cells[&edge] = states::EdgeState::Unknown;
+    }
+    states::Tokenset::Edges(cells)
+}
+
 #[cfg(test)]
 mod tests {
     use super::initialize;
@@ -57,18 +110,18 @@ mod tests {
         let grid = setting.add_grid(4, 4);
         let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
         let givens = presets::givens(&[
-            "5...".to_string(),
-            "..6.".to_string(),
+            "4...".to_string(),
+            "..3.".to_string(),
             ".1..".to_string(),
             "....".to_string(),
         ]);
-        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens);
+        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens)?;
 
         let result = initialize(&setting, Purpose::Solving)?;
         let candidates = states::CellState::Candidates("1234".chars().collect());
         let tokensets = vec![states::Tokenset::Symbols(vec![
             vec![
-                states::CellState::Set('5'),
+                states::CellState::Set('4'),
                 candidates.clone(),
                 candidates.clone(),
                 candidates.clone(),
@@ -76,7 +129,7 @@ mod tests {
             vec![
                 candidates.clone(),
                 candidates.clone(),
-                states::CellState::Set('6'),
+                states::CellState::Set('3'),
                 candidates.clone(),
             ],
             vec![
@@ -103,17 +156,17 @@ mod tests {
         let grid = setting.add_grid(4, 4);
         let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
         let givens = presets::givens(&[
-            "5...".to_string(),
-            "..6.".to_string(),
+            "4...".to_string(),
+            "..3.".to_string(),
             ".1..".to_string(),
             "....".to_string(),
         ]);
-        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens);
+        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens)?;
 
         let result = initialize(&setting, Purpose::Playing)?;
         let tokensets = vec![states::Tokenset::Symbols(vec![
             vec![
-                states::CellState::Set('5'),
+                states::CellState::Set('4'),
                 states::CellState::Empty,
                 states::CellState::Empty,
                 states::CellState::Empty,
@@ -121,7 +174,7 @@ mod tests {
             vec![
                 states::CellState::Empty,
                 states::CellState::Empty,
-                states::CellState::Set('6'),
+                states::CellState::Set('3'),
                 states::CellState::Empty,
             ],
             vec![
@@ -141,4 +194,84 @@ mod tests {
         assert_eq!(result, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_restrictions() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = sudoku::add_symbolset(&mut setting, grid, 4, Vec::new(), Vec::new())?;
+        setting.rules.push(settings::Rule::CellRestriction {
+            tokenset,
+            restrictions: vec![
+                settings::CellRestriction {
+                    cell: shapes::Cell(0, 0),
+                    restriction: settings::Restriction::Even,
+                },
+                settings::CellRestriction {
+                    cell: shapes::Cell(1, 0),
+                    restriction: settings::Restriction::High,
+                },
+            ],
+        });
+
+        let solving = initialize(&setting, Purpose::Solving)?;
+        let states::Tokenset::Symbols(cells) = &solving.tokensets[0] else {
+            panic!("expected a symbol tokenset");
+        };
+        assert_eq!(cells[0][0], states::CellState::Candidates("24".to_string()));
+        assert_eq!(cells[0][1], states::CellState::Candidates("34".to_string()));
+        assert_eq!(
+            cells[0][2],
+            states::CellState::Candidates("1234".to_string())
+        );
+
+        let playing = initialize(&setting, Purpose::Playing)?;
+        let states::Tokenset::Symbols(cells) = &playing.tokensets[0] else {
+            panic!("expected a symbol tokenset");
+        };
+        assert_eq!(cells[0][0], states::CellState::Empty);
+        Ok(())
+    }
+
+    #[test]
+    fn test_masked_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let missing = std::iter::once(shapes::Cell(2, 0)).collect();
+        let grid = setting.add_masked_grid(2, 3, missing);
+        sudoku::add_symbolset(&mut setting, grid, 3, Vec::new(), Vec::new())?;
+
+        let result = initialize(&setting, Purpose::Solving)?;
+        let candidates = states::CellState::Candidates("123".to_string());
+        let tokensets = vec![states::Tokenset::Symbols(vec![
+            vec![
+                candidates.clone(),
+                candidates.clone(),
+                states::CellState::Empty,
+            ],
+            vec![candidates.clone(), candidates.clone(), candidates.clone()],
+        ])];
+        assert_eq!(result, states::State { tokensets });
+        Ok(())
+    }
+
+    #[test]
+    fn test_shading() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(1, 3);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Shading { grid });
+        setting.rules.push(settings::Rule::Shading {
+            tokenset,
+            givens: vec![settings::GivenShade {
+                shaded: false,
+                cell: shapes::Cell(2, 0),
+            }],
+        });
+
+        let result = initialize(&setting, Purpose::Playing)?;
+        let expected = states::State {
+            tokensets: vec![states::shadeset(&["..o".to_string()])],
+        };
+        assert_eq!(result, expected);
+        Ok(())
+    }
 }
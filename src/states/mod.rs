@@ -7,9 +7,16 @@ mod tokensets;
 pub use apply_constraints::apply_constraints;
 pub use base::State;
 pub use initialization::initialize;
+pub use initialization::Purpose;
 pub use status::compute_status;
 pub use status::Status;
+pub use tokensets::edgeset;
+pub use tokensets::shadeset;
 pub use tokensets::symbolset;
 pub use tokensets::CellGrid;
 pub use tokensets::CellState;
+pub use tokensets::EdgeGrid;
+pub use tokensets::EdgeState;
+pub use tokensets::ShadeGrid;
+pub use tokensets::ShadeState;
 pub use tokensets::Tokenset;
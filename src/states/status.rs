@@ -20,11 +20,138 @@ pub fn compute_status(state: &states::State, constraints: &[settings::Constraint
                 cell,
                 symbol,
             } => check_given_symbol(state, *tokenset, cell, *symbol)?,
+            settings::Constraint::GivenShade {
+                tokenset,
+                cell,
+                shaded,
+            } => check_given_shade(state, *tokenset, cell, *shaded)?,
+            settings::Constraint::ShadeCount {
+                tokenset,
+                region,
+                count,
+            } => check_shade_count(state, *tokenset, region, *count)?,
+            settings::Constraint::NotBothShaded {
+                tokenset,
+                first,
+                second,
+            } => check_not_both_shaded(state, *tokenset, first, second)?,
+            settings::Constraint::NotAllShaded { tokenset, region } => {
+                check_not_all_shaded(state, *tokenset, region)?
+            }
+            settings::Constraint::ShadedConnected {
+                tokenset,
+                region,
+                adjacency,
+            } => check_shaded_connected(state, *tokenset, region, adjacency)?,
+            settings::Constraint::NumberedIslands {
+                tokenset,
+                region,
+                adjacency,
+                clues,
+            } => check_numbered_islands(state, *tokenset, region, adjacency, clues)?,
+            settings::Constraint::GivenEdge { tokenset, edge, on } => {
+                check_given_edge(state, *tokenset, edge, *on)?
+            }
+            settings::Constraint::SingleLoop { tokenset, edges } => {
+                check_single_loop(state, *tokenset, edges)?
+            }
             settings::Constraint::SymbolRepartition {
                 tokenset,
                 region,
                 repartition,
             } => check_symbol_repartition(state, *tokenset, region, repartition)?,
+            settings::Constraint::Sandwich {
+                tokenset,
+                region,
+                crusts,
+                values,
+                sum,
+            } => check_sandwich(state, *tokenset, region, *crusts, values, *sum)?,
+            settings::Constraint::RegionSum {
+                tokenset,
+                region,
+                values,
+                sum,
+                distinct,
+            } => check_region_sum(state, *tokenset, region, values, *sum, *distinct)?,
+            settings::Constraint::Different {
+                tokenset,
+                first,
+                second,
+            } => check_different(state, *tokenset, first, second)?,
+            settings::Constraint::NonConsecutive {
+                tokenset,
+                first,
+                second,
+                order,
+            } => check_non_consecutive(state, *tokenset, first, second, order)?,
+            settings::Constraint::AllowedSymbols {
+                tokenset,
+                cell,
+                symbols,
+            } => check_allowed_symbols(state, *tokenset, cell, symbols)?,
+            settings::Constraint::Less {
+                tokenset,
+                lower,
+                greater,
+                order,
+            } => check_less(state, *tokenset, lower, greater, order)?,
+            settings::Constraint::Renban {
+                tokenset,
+                region,
+                order,
+            } => check_renban(state, *tokenset, region, order)?,
+            settings::Constraint::MinDifference {
+                tokenset,
+                first,
+                second,
+                values,
+                difference,
+            } => check_min_difference(state, *tokenset, first, second, values, *difference)?,
+            settings::Constraint::SameSymbol {
+                tokenset,
+                first,
+                second,
+            } => check_same_symbol(state, *tokenset, first, second)?,
+            settings::Constraint::EqualSums {
+                tokenset,
+                regions,
+                values,
+            } => check_equal_sums(state, *tokenset, regions, values)?,
+            settings::Constraint::Between {
+                tokenset,
+                ends,
+                region,
+                order,
+            } => check_between(state, *tokenset, ends, region, order)?,
+            settings::Constraint::DistinctClasses {
+                tokenset,
+                region,
+                classes,
+            } => check_distinct_classes(state, *tokenset, region, classes)?,
+            settings::Constraint::Visibility {
+                tokenset,
+                region,
+                order,
+                count,
+            } => check_visibility(state, *tokenset, region, order, *count)?,
+            settings::Constraint::XSum {
+                tokenset,
+                region,
+                values,
+                sum,
+            } => check_x_sum(state, *tokenset, region, values, *sum)?,
+            settings::Constraint::MustContain {
+                tokenset,
+                region,
+                symbols,
+            } => check_must_contain(state, *tokenset, region, symbols)?,
+            settings::Constraint::SharedCell {
+                tokenset,
+                cell,
+                other_tokenset,
+                other_cell,
+            } => check_shared_cell(state, *tokenset, cell, *other_tokenset, other_cell)?,
         };
         match status {
             Status::Ongoing => complete = false,
@@ -32,7 +159,21 @@ pub fn compute_status(state: &states::State, constraints: &[settings::Constraint
             Status::Valid => (),
         }
     }
-    Ok(if complete {
+    // Shading cells and edges are not all covered by constraints.
+    let decided = state.tokensets.iter().all(|tokenset| match tokenset {
+        states::Tokenset::Shading(cells) => cells
+            .iter()
+            .flatten()
+            .all(|cell_state| *cell_state != states::ShadeState::Unknown),
+        states::Tokenset::Edges(cells) => cells
+            .horizontal
+            .iter()
+            .chain(&cells.vertical)
+            .flatten()
+            .all(|edge_state| *edge_state != states::EdgeState::Unknown),
+        _ => true,
+    });
+    Ok(if complete && decided {
         Status::Valid
     } else {
         Status::Ongoing
@@ -60,6 +201,325 @@ fn check_given_symbol(
                 _ => Ok(Status::Ongoing),
             }
         }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_given_shade(
+    state: &states::State,
+    tokenset: usize,
+    cell: &shapes::Cell,
+    shaded: bool,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Shading(cells) => Ok(match (cells[cell], shaded) {
+            (states::ShadeState::Unknown, _) => Status::Ongoing,
+            (states::ShadeState::Shaded, true) | (states::ShadeState::Unshaded, false) => {
+                Status::Valid
+            }
+            _ => Status::Invalid,
+        }),
+        _ => Err("constraint expects a shading tokenset".into()),
+    }
+}
+
+fn check_shade_count(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    count: usize,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Shading(cells) => {
+            let count_state = |expected: states::ShadeState| {
+                region
+                    .cells
+                    .iter()
+                    .filter(|cell| cells[*cell] == expected)
+                    .count()
+            };
+            let shaded = count_state(states::ShadeState::Shaded);
+            let unknown = count_state(states::ShadeState::Unknown);
+            Ok(if shaded > count || shaded + unknown < count {
+                Status::Invalid
+            } else if unknown > 0 {
+                Status::Ongoing
+            } else {
+                Status::Valid
+            })
+        }
+        _ => Err("constraint expects a shading tokenset".into()),
+    }
+}
+
+fn check_not_both_shaded(
+    state: &states::State,
+    tokenset: usize,
+    first: &shapes::Cell,
+    second: &shapes::Cell,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Shading(cells) => Ok(match (cells[first], cells[second]) {
+            (states::ShadeState::Shaded, states::ShadeState::Shaded) => Status::Invalid,
+            (states::ShadeState::Unknown, _) | (_, states::ShadeState::Unknown) => Status::Ongoing,
+            _ => Status::Valid,
+        }),
+        _ => Err("constraint expects a shading tokenset".into()),
+    }
+}
+
+fn check_not_all_shaded(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+) -> ResultStatus {
+    let cells = match &state.tokensets[tokenset] {
+        states::Tokenset::Shading(cells) => cells,
+        _ => return Err("constraint expects a shading tokenset".into()),
+    };
+    Ok(
+        if region
+            .cells
+            .iter()
+            .all(|cell| cells[cell] == states::ShadeState::Shaded)
+        {
+            Status::Invalid
+        } else if region
+            .cells
+            .iter()
+            .any(|cell| cells[cell] == states::ShadeState::Unknown)
+        {
+            Status::Ongoing
+        } else {
+            Status::Valid
+        },
+    )
+}
+
+fn check_shaded_connected(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    adjacency: &[(shapes::Cell, shapes::Cell)],
+) -> ResultStatus {
+    let cells = match &state.tokensets[tokenset] {
+        states::Tokenset::Shading(cells) => cells,
+        _ => return Err("constraint expects a shading tokenset".into()),
+    };
+    // Shaded cells may only connect through cells which are not unshaded.
+    let open = shapes::Region {
+        cells: region
+            .cells
+            .iter()
+            .filter(|cell| cells[*cell] != states::ShadeState::Unshaded)
+            .cloned()
+            .collect(),
+    };
+    let shaded_components = open
+        .components(adjacency)
+        .iter()
+        .filter(|component| {
+            component
+                .cells
+                .iter()
+                .any(|cell| cells[cell] == states::ShadeState::Shaded)
+        })
+        .count();
+    Ok(if shaded_components > 1 {
+        Status::Invalid
+    } else if region
+        .cells
+        .iter()
+        .any(|cell| cells[cell] == states::ShadeState::Unknown)
+    {
+        Status::Ongoing
+    } else {
+        Status::Valid
+    })
+}
+
+fn check_numbered_islands(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    adjacency: &[(shapes::Cell, shapes::Cell)],
+    clues: &[(shapes::Cell, usize)],
+) -> ResultStatus {
+    let cells = match &state.tokensets[tokenset] {
+        states::Tokenset::Shading(cells) => cells,
+        _ => return Err("constraint expects a shading tokenset".into()),
+    };
+    if clues
+        .iter()
+        .any(|(cell, _)| cells[cell] == states::ShadeState::Shaded)
+    {
+        return Ok(Status::Invalid);
+    }
+    let clue_sizes = |component: &shapes::Region| {
+        clues
+            .iter()
+            .filter(|(cell, _)| component.cells.contains(cell))
+            .map(|(_, size)| *size)
+            .collect::<Vec<_>>()
+    };
+    let filtered = |keep: &dyn Fn(states::ShadeState) -> bool| shapes::Region {
+        cells: region
+            .cells
+            .iter()
+            .filter(|cell| keep(cells[*cell]))
+            .cloned()
+            .collect(),
+    };
+    let islands = filtered(&|shade| shade == states::ShadeState::Unshaded);
+    for island in islands.components(adjacency) {
+        let closed = !adjacency.iter().any(|(first, second)| {
+            (island.cells.contains(first) && cells[second] == states::ShadeState::Unknown)
+                || (island.cells.contains(second) && cells[first] == states::ShadeState::Unknown)
+        });
+        let invalid = match clue_sizes(&island)[..] {
+            [] => closed,
+            [size] => island.cells.len() > size || (closed && island.cells.len() != size),
+            _ => true,
+        };
+        if invalid {
+            return Ok(Status::Invalid);
+        }
+    }
+    // Islands can only grow through unknown cells.
+    let open = filtered(&|shade| shade != states::ShadeState::Shaded);
+    for area in open.components(adjacency) {
+        let invalid = match clue_sizes(&area)[..] {
+            [] => area
+                .cells
+                .iter()
+                .any(|cell| cells[cell] == states::ShadeState::Unshaded),
+            [size] => area.cells.len() < size,
+            _ => false,
+        };
+        if invalid {
+            return Ok(Status::Invalid);
+        }
+    }
+    Ok(
+        if region
+            .cells
+            .iter()
+            .any(|cell| cells[cell] == states::ShadeState::Unknown)
+        {
+            Status::Ongoing
+        } else {
+            Status::Valid
+        },
+    )
+}
+
+fn check_given_edge(
+    state: &states::State,
+    tokenset: usize,
+    edge: &shapes::Edge,
+    on: bool,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Edges(cells) => Ok(match (cells[edge], on) {
+            (states::EdgeState::Unknown, _) => Status::Ongoing,
+            (states::EdgeState::On, true) | (states::EdgeState::Off, false) => Status::Valid,
+            _ => Status::Invalid,
+        }),
+        _ => Err("constraint expects an edge tokenset".into()),
+    }
+}
+
+fn check_single_loop(
+    state: &states::State,
+    tokenset: usize,
+    edges: &[shapes::Edge],
+) -> ResultStatus {
+    let cells = match &state.tokensets[tokenset] {
+        states::Tokenset::Edges(cells) => cells,
+        _ => return Err("constraint expects an edge tokenset".into()),
+    };
+    let mut neighbours = std::collections::HashMap::new();
+    let mut undecided = std::collections::HashSet::new();
+    for edge in edges {
+        let [first, second] = edge.ends();
+        match cells[edge] {
+            states::EdgeState::On => {
+                neighbours
+                    .entry(first)
+                    .or_insert_with(Vec::new)
+                    .push(second);
+                neighbours
+                    .entry(second)
+                    .or_insert_with(Vec::new)
+                    .push(first);
+            }
+            states::EdgeState::Unknown => {
+                undecided.insert(first);
+                undecided.insert(second);
+            }
+            states::EdgeState::Off => (),
+        }
+    }
+    let dead_end = neighbours.iter().any(|(point, others)| {
+        others.len() > 2 || (others.len() == 1 && !undecided.contains(point))
+    });
+    if dead_end {
+        return Ok(Status::Invalid);
+    }
+    let mut visited = std::collections::HashSet::new();
+    let mut closed_loops = 0;
+    let mut components = 0;
+    for start in neighbours.keys() {
+        if visited.contains(start) {
+            continue;
+        }
+        components += 1;
+        let mut closed = true;
+        let mut to_visit = vec![start];
+        while let Some(point) = to_visit.pop() {
+            if !visited.insert(point) {
+                continue;
+            }
+            let others = &neighbours[point];
+            closed &= others.len() == 2;
+            to_visit.extend(others);
+        }
+        if closed {
+            closed_loops += 1;
+        }
+    }
+    Ok(if closed_loops > 0 && components > 1 {
+        Status::Invalid
+    } else if !undecided.is_empty() {
+        Status::Ongoing
+    } else if closed_loops == 1 {
+        Status::Valid
+    } else {
+        Status::Invalid
+    })
+}
+
+fn check_allowed_symbols(
+    state: &states::State,
+    tokenset: usize,
+    cell: &shapes::Cell,
+    symbols: &str,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => match &cells[cell] {
+            states::CellState::Set(value) => Ok(if symbols.contains(*value) {
+                Status::Valid
+            } else {
+                Status::Invalid
+            }),
+            _ => Ok(Status::Ongoing),
+        },
+        _ => Err("constraint expects a symbol tokenset".into()),
     }
 }
 
@@ -98,6 +558,478 @@ fn check_symbol_repartition(
                 Status::Ongoing
             })
         }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_sandwich(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    crusts: (settings::SymbolType, settings::SymbolType),
+    values: &settings::SymbolValues,
+    sum: usize,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            // Unset cells count as zero until they are filled.
+            let symbols = region
+                .cells
+                .iter()
+                .map(|cell| match &cells[cell] {
+                    states::CellState::Set(value) => Some(*value),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let set_symbols = symbols.iter().flatten().cloned().collect::<Vec<_>>();
+            let total = match settings::sandwich_sum(&set_symbols, crusts, values) {
+                Some(total) => total,
+                None => return Ok(Status::Ongoing),
+            };
+            let is_crust = |symbol: &&Option<settings::SymbolType>| {
+                **symbol == Some(crusts.0) || **symbol == Some(crusts.1)
+            };
+            let completed = symbols
+                .iter()
+                .skip_while(|symbol| !is_crust(symbol))
+                .skip(1)
+                .take_while(|symbol| !is_crust(symbol))
+                .all(Option::is_some);
+            Ok(if total > sum || (completed && total < sum) {
+                Status::Invalid
+            } else if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_region_sum(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    values: &settings::SymbolValues,
+    sum: usize,
+    distinct: bool,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let mut found = std::collections::HashSet::new();
+            let mut total = 0;
+            let mut completed = true;
+            for cell in &region.cells {
+                match &cells[cell] {
+                    states::CellState::Set(value) => {
+                        if !found.insert(*value) && distinct {
+                            return Ok(Status::Invalid);
+                        }
+                        total += values.get(value).unwrap_or(&0);
+                    }
+                    _ => completed = false,
+                }
+            }
+            Ok(if total > sum || (completed && total < sum) {
+                Status::Invalid
+            } else if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_different(
+    state: &states::State,
+    tokenset: usize,
+    first: &shapes::Cell,
+    second: &shapes::Cell,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => match (&cells[first], &cells[second]) {
+            (states::CellState::Set(first), states::CellState::Set(second)) => {
+                Ok(if first == second {
+                    Status::Invalid
+                } else {
+                    Status::Valid
+                })
+            }
+            _ => Ok(Status::Ongoing),
+        },
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_non_consecutive(
+    state: &states::State,
+    tokenset: usize,
+    first: &shapes::Cell,
+    second: &shapes::Cell,
+    order: &settings::SymbolOrder,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => match (&cells[first], &cells[second]) {
+            (states::CellState::Set(first), states::CellState::Set(second)) => {
+                Ok(if settings::are_consecutive(order, *first, *second) {
+                    Status::Invalid
+                } else {
+                    Status::Valid
+                })
+            }
+            _ => Ok(Status::Ongoing),
+        },
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_less(
+    state: &states::State,
+    tokenset: usize,
+    lower: &shapes::Cell,
+    greater: &shapes::Cell,
+    order: &settings::SymbolOrder,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => match (&cells[lower], &cells[greater]) {
+            (states::CellState::Set(lower), states::CellState::Set(greater)) => {
+                Ok(match (order.get(lower), order.get(greater)) {
+                    (Some(lower), Some(greater)) if lower < greater => Status::Valid,
+                    _ => Status::Invalid,
+                })
+            }
+            _ => Ok(Status::Ongoing),
+        },
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_renban(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    order: &settings::SymbolOrder,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let mut ranks = std::collections::HashSet::new();
+            let mut completed = true;
+            for cell in &region.cells {
+                match &cells[cell] {
+                    states::CellState::Set(value) => match order.get(value) {
+                        Some(rank) if ranks.insert(*rank) => (),
+                        _ => return Ok(Status::Invalid),
+                    },
+                    _ => completed = false,
+                }
+            }
+            let span = match (ranks.iter().min(), ranks.iter().max()) {
+                (Some(min), Some(max)) => max - min,
+                _ => 0,
+            };
+            Ok(if span >= region.cells.len() {
+                Status::Invalid
+            } else if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_min_difference(
+    state: &states::State,
+    tokenset: usize,
+    first: &shapes::Cell,
+    second: &shapes::Cell,
+    values: &settings::SymbolValues,
+    difference: usize,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => match (&cells[first], &cells[second]) {
+            (states::CellState::Set(first), states::CellState::Set(second)) => {
+                match (values.get(first), values.get(second)) {
+                    (Some(first), Some(second))
+                        if (*first as isize - *second as isize).abs() >= difference as isize =>
+                    {
+                        Ok(Status::Valid)
+                    }
+                    _ => Ok(Status::Invalid),
+                }
+            }
+            _ => Ok(Status::Ongoing),
+        },
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_same_symbol(
+    state: &states::State,
+    tokenset: usize,
+    first: &shapes::Cell,
+    second: &shapes::Cell,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => match (&cells[first], &cells[second]) {
+            (states::CellState::Set(first), states::CellState::Set(second)) => {
+                Ok(if first == second {
+                    Status::Valid
+                } else {
+                    Status::Invalid
+                })
+            }
+            _ => Ok(Status::Ongoing),
+        },
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_shared_cell(
+    state: &states::State,
+    tokenset: usize,
+    cell: &shapes::Cell,
+    other_tokenset: usize,
+    other_cell: &shapes::Cell,
+) -> ResultStatus {
+    let (tokenset, other_tokenset) = (&state.tokensets[tokenset], &state.tokensets[other_tokenset]);
+    match (tokenset, other_tokenset) {
+        (states::Tokenset::Symbols(cells), states::Tokenset::Symbols(other_cells)) => {
+            match (&cells[cell], &other_cells[other_cell]) {
+                (states::CellState::Set(first), states::CellState::Set(second)) => {
+                    Ok(if first == second {
+                        Status::Valid
+                    } else {
+                        Status::Invalid
+                    })
+                }
+                _ => Ok(Status::Ongoing),
+            }
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_equal_sums(
+    state: &states::State,
+    tokenset: usize,
+    regions: &[shapes::Region],
+    values: &settings::SymbolValues,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let mut sums = std::collections::HashSet::new();
+            let mut completed = true;
+            for region in regions {
+                let mut total = 0;
+                let mut region_completed = true;
+                for cell in &region.cells {
+                    match &cells[cell] {
+                        states::CellState::Set(value) => total += values.get(value).unwrap_or(&0),
+                        _ => region_completed = false,
+                    }
+                }
+                if region_completed {
+                    sums.insert(total);
+                } else {
+                    completed = false;
+                }
+            }
+            Ok(if sums.len() > 1 {
+                Status::Invalid
+            } else if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_between(
+    state: &states::State,
+    tokenset: usize,
+    ends: &(shapes::Cell, shapes::Cell),
+    region: &shapes::Region,
+    order: &settings::SymbolOrder,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let (low, high) = match (&cells[&ends.0], &cells[&ends.1]) {
+                (states::CellState::Set(first), states::CellState::Set(second)) => {
+                    match (order.get(first), order.get(second)) {
+                        (Some(first), Some(second)) => (*first.min(second), *first.max(second)),
+                        _ => return Ok(Status::Invalid),
+                    }
+                }
+                _ => return Ok(Status::Ongoing),
+            };
+            let mut completed = true;
+            for cell in &region.cells {
+                match &cells[cell] {
+                    states::CellState::Set(value) => match order.get(value) {
+                        Some(rank) if low < *rank && *rank < high => (),
+                        _ => return Ok(Status::Invalid),
+                    },
+                    _ => completed = false,
+                }
+            }
+            Ok(if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_distinct_classes(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    classes: &[String],
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let mut found = std::collections::HashSet::new();
+            let mut completed = true;
+            for cell in &region.cells {
+                match &cells[cell] {
+                    states::CellState::Set(value) => {
+                        match classes.iter().position(|class| class.contains(*value)) {
+                            Some(class) if found.insert(class) => (),
+                            _ => return Ok(Status::Invalid),
+                        }
+                    }
+                    _ => completed = false,
+                }
+            }
+            Ok(if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_visibility(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    order: &settings::SymbolOrder,
+    count: usize,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let mut symbols = Vec::new();
+            for cell in &region.cells {
+                match &cells[cell] {
+                    states::CellState::Set(value) => symbols.push(*value),
+                    _ => break,
+                }
+            }
+            let tallest = order.iter().max_by_key(|(_, rank)| **rank);
+            let completed = symbols.len() == region.cells.len()
+                || tallest.is_some_and(|(symbol, _)| symbols.contains(symbol));
+            Ok(if !completed {
+                Status::Ongoing
+            } else if settings::visible_count(&symbols, order) == count {
+                Status::Valid
+            } else {
+                Status::Invalid
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_x_sum(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    values: &settings::SymbolValues,
+    sum: usize,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let count = match region.cells.first().map(|cell| &cells[cell]) {
+                Some(states::CellState::Set(value)) => *values.get(value).unwrap_or(&0),
+                _ => return Ok(Status::Ongoing),
+            };
+            if count == 0 || count > region.cells.len() {
+                return Ok(Status::Invalid);
+            }
+            let mut total = 0;
+            let mut completed = true;
+            for cell in &region.cells[..count] {
+                match &cells[cell] {
+                    states::CellState::Set(value) => total += values.get(value).unwrap_or(&0),
+                    _ => completed = false,
+                }
+            }
+            Ok(if total > sum || (completed && total < sum) {
+                Status::Invalid
+            } else if completed {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
+    }
+}
+
+fn check_must_contain(
+    state: &states::State,
+    tokenset: usize,
+    region: &shapes::Region,
+    symbols: &str,
+) -> ResultStatus {
+    let tokenset = &state.tokensets[tokenset];
+    match tokenset {
+        states::Tokenset::Symbols(cells) => {
+            let mut missing = symbols.chars().collect::<Vec<_>>();
+            let mut unset = 0;
+            for cell in &region.cells {
+                match &cells[cell] {
+                    states::CellState::Set(value) => {
+                        if let Some(index) = missing.iter().position(|symbol| symbol == value) {
+                            missing.remove(index);
+                        }
+                    }
+                    _ => unset += 1,
+                }
+            }
+            Ok(if missing.len() > unset {
+                Status::Invalid
+            } else if unset == 0 {
+                Status::Valid
+            } else {
+                Status::Ongoing
+            })
+        }
+        _ => Err("constraint expects a symbol tokenset".into()),
     }
 }
 
@@ -110,7 +1042,7 @@ mod tests {
     use crate::shapes;
     use crate::states;
 
-    fn sample_setting() -> settings::PuzzleSetting {
+    fn sample_setting() -> Result<settings::PuzzleSetting, Box<dyn std::error::Error>> {
         let mut setting = settings::PuzzleSetting::default();
         let grid = setting.add_grid(4, 4);
         let regions = shapes::Region::grid_boxes(2, 2, 2, 2).collect();
@@ -120,13 +1052,73 @@ mod tests {
             ".1..".to_string(),
             "....".to_string(),
         ]);
-        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens);
-        setting
+        sudoku::add_symbolset(&mut setting, grid, 4, regions, givens)?;
+        Ok(setting)
+    }
+
+    fn symbol_status(constraints: &[settings::Constraint], rows: &[&str]) -> ResultStatus {
+        let rows = rows.iter().map(|row| row.to_string()).collect::<Vec<_>>();
+        let state = states::State {
+            tokensets: vec![states::symbolset(&rows, states::CellState::Empty)],
+        };
+        compute_status(&state, constraints)
+    }
+
+    fn shade_status(constraints: &[settings::Constraint], rows: &[&str]) -> ResultStatus {
+        let rows = rows.iter().map(|row| row.to_string()).collect::<Vec<_>>();
+        let state = states::State {
+            tokensets: vec![states::shadeset(&rows)],
+        };
+        compute_status(&state, constraints)
+    }
+
+    fn edge_status(
+        constraints: &[settings::Constraint],
+        horizontal: &[&str],
+        vertical: &[&str],
+    ) -> ResultStatus {
+        let horizontal = horizontal
+            .iter()
+            .map(|row| row.to_string())
+            .collect::<Vec<_>>();
+        let vertical = vertical
+            .iter()
+            .map(|row| row.to_string())
+            .collect::<Vec<_>>();
+        let state = states::State {
+            tokensets: vec![states::edgeset(&horizontal, &vertical)],
+        };
+        compute_status(&state, constraints)
+    }
+
+    fn line_constraints(
+        kind: settings::LineKind,
+    ) -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(6, 6);
+        let regions = shapes::Region::grid_boxes(2, 3, 3, 2).collect();
+        sudoku::add_symbolset(&mut setting, grid, 6, regions, Vec::new())?;
+        setting.rules.push(settings::Rule::Line {
+            tokenset: 0,
+            kind,
+            path: shapes::Path::new((0..4).map(|x| shapes::Cell(x, 0)).collect())?,
+        });
+        let mut constraints = settings::list_constraints(&setting)?;
+        constraints.retain(|constraint| {
+            !matches!(constraint, settings::Constraint::SymbolRepartition { .. })
+        });
+        Ok(constraints)
+    }
+
+    fn star_constraints() -> Result<Vec<settings::Constraint>, Box<dyn std::error::Error>> {
+        let layout = ["AABB", "AABB", "CCDD", "CCDD"].map(|row| row.to_string());
+        let setting = presets::star_battle::setting(&layout, 1)?;
+        settings::list_constraints(&setting)
     }
 
     #[test]
     fn test_start_position() -> Result<(), Box<dyn std::error::Error>> {
-        let setting = sample_setting();
+        let setting = sample_setting()?;
         let constraints = settings::list_constraints(&setting)?;
         let state = states::State {
             tokensets: vec![states::symbolset(
@@ -146,7 +1138,7 @@ mod tests {
 
     #[test]
     fn test_invalid_given() -> Result<(), Box<dyn std::error::Error>> {
-        let setting = sample_setting();
+        let setting = sample_setting()?;
         let constraints = settings::list_constraints(&setting)?;
         let state = states::State {
             tokensets: vec![states::symbolset(
@@ -166,7 +1158,7 @@ mod tests {
 
     #[test]
     fn test_repartition() -> Result<(), Box<dyn std::error::Error>> {
-        let setting = sample_setting();
+        let setting = sample_setting()?;
         let constraints = settings::list_constraints(&setting)?;
         let state = states::State {
             tokensets: vec![states::symbolset(
@@ -186,7 +1178,7 @@ mod tests {
 
     #[test]
     fn test_finished() -> Result<(), Box<dyn std::error::Error>> {
-        let setting = sample_setting();
+        let setting = sample_setting()?;
         let constraints = settings::list_constraints(&setting)?;
         let state = states::State {
             tokensets: vec![states::symbolset(
@@ -203,4 +1195,427 @@ mod tests {
         assert_eq!(result, Status::Valid);
         Ok(())
     }
+
+    #[test]
+    fn test_sandwich() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting.rules.push(settings::Rule::Sandwich {
+            tokenset: 0,
+            clues: vec![settings::OutsideClue {
+                side: settings::Side::Left,
+                index: 0,
+                value: 3,
+            }],
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["23.1", "..3.", ".1..", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["2.41", "..3.", ".1..", "...."])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["4231", "..3.", ".1..", "...."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_region_sum() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = vec![settings::Constraint::RegionSum {
+            tokenset: 0,
+            region: shapes::Region {
+                cells: (0..3).map(|x| shapes::Cell(x, 0)).collect(),
+            },
+            values: settings::digit_values("1234"),
+            sum: 6,
+            distinct: false,
+        }];
+        assert_eq!(symbol_status(&constraints, &["22.."])?, Status::Ongoing);
+        assert_eq!(symbol_status(&constraints, &["222."])?, Status::Valid);
+        assert_eq!(symbol_status(&constraints, &["4.3."])?, Status::Invalid);
+        assert_eq!(symbol_status(&constraints, &["113."])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_anti_knight() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting.rules.push(settings::Rule::AntiChess {
+            tokenset: 0,
+            moves: shapes::Moves::Knight,
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", ".3.."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_consecutive() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting
+            .rules
+            .push(settings::Rule::NonConsecutive { tokenset: 0 });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["24..", "..3.", ".1..", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["23..", "..3.", ".1..", "...."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_less() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = vec![settings::Constraint::Less {
+            tokenset: 0,
+            lower: shapes::Cell(0, 0),
+            greater: shapes::Cell(1, 0),
+            order: settings::TokenSet::Symbols {
+                grid: 0,
+                candidates: "1234".to_string(),
+                values: settings::SymbolValues::new(),
+                order: settings::SymbolOrder::new(),
+            }
+            .symbol_order(),
+        }];
+        assert_eq!(symbol_status(&constraints, &["2."])?, Status::Ongoing);
+        assert_eq!(symbol_status(&constraints, &["23"])?, Status::Valid);
+        assert_eq!(symbol_status(&constraints, &["32"])?, Status::Invalid);
+        assert_eq!(symbol_status(&constraints, &["Z2"])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_allowed_symbols() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting.rules.push(settings::Rule::CellRestriction {
+            tokenset: 0,
+            restrictions: vec![settings::CellRestriction {
+                cell: shapes::Cell(1, 0),
+                restriction: settings::Restriction::Odd,
+            }],
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["23..", "..3.", ".1..", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["24..", "..3.", ".1..", "...."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_renban() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting.rules.push(settings::Rule::Line {
+            tokenset: 0,
+            kind: settings::LineKind::Renban,
+            path: shapes::Path::new((0..3).map(|x| shapes::Cell(x, 3)).collect())?,
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "1.4."])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "3.4."])?,
+            Status::Ongoing
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dutch_whispers() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting.rules.push(settings::Rule::Line {
+            tokenset: 0,
+            kind: settings::LineKind::DutchWhispers,
+            path: shapes::Path::new((0..3).map(|x| shapes::Cell(x, 3)).collect())?,
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "4..."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "42.."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_palindrome() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = sample_setting()?;
+        setting.rules.push(settings::Rule::Line {
+            tokenset: 0,
+            kind: settings::LineKind::Palindrome,
+            path: shapes::Path::new((0..3).map(|x| shapes::Cell(x, 3)).collect())?,
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "3.4."])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["2...", "..3.", ".1..", "3..."])?,
+            Status::Ongoing
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_between_line() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = line_constraints(settings::LineKind::Between)?;
+        assert_eq!(symbol_status(&constraints, &["25.6"])?, Status::Ongoing);
+        assert_eq!(symbol_status(&constraints, &["2536"])?, Status::Valid);
+        assert_eq!(symbol_status(&constraints, &["2516"])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_entropic_line() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = line_constraints(settings::LineKind::Entropic)?;
+        assert_eq!(symbol_status(&constraints, &["1352"])?, Status::Valid);
+        assert_eq!(symbol_status(&constraints, &["152."])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_modular_line() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = line_constraints(settings::LineKind::Modular)?;
+        assert_eq!(symbol_status(&constraints, &["142."])?, Status::Invalid);
+        assert_eq!(symbol_status(&constraints, &["1236"])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_region_sum_line() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = line_constraints(settings::LineKind::RegionSum)?;
+        assert_eq!(symbol_status(&constraints, &["42.6"])?, Status::Ongoing);
+        assert_eq!(symbol_status(&constraints, &["4236"])?, Status::Invalid);
+        assert_eq!(symbol_status(&constraints, &["3216"])?, Status::Valid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_outside_sums() -> Result<(), Box<dyn std::error::Error>> {
+        let region = shapes::Region {
+            cells: (0..4).map(|x| shapes::Cell(x, 0)).collect(),
+        };
+        let constraints = vec![
+            settings::Constraint::XSum {
+                tokenset: 0,
+                region: region.clone(),
+                values: settings::digit_values("1234"),
+                sum: 6,
+            },
+            settings::Constraint::MustContain {
+                tokenset: 0,
+                region,
+                symbols: "24".to_string(),
+            },
+        ];
+        assert_eq!(symbol_status(&constraints, &["24.."])?, Status::Ongoing);
+        assert_eq!(symbol_status(&constraints, &["2413"])?, Status::Valid);
+        assert_eq!(symbol_status(&constraints, &["341."])?, Status::Invalid);
+        assert_eq!(symbol_status(&constraints, &["213."])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_visibility() -> Result<(), Box<dyn std::error::Error>> {
+        let givens = ["...", "...", "..."].map(|row| row.to_string());
+        let clues = vec![settings::OutsideClue {
+            side: settings::Side::Left,
+            index: 0,
+            value: 2,
+        }];
+        let setting = presets::skyscrapers::setting(&givens, clues)?;
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            symbol_status(&constraints, &["1..", "...", "..."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["132", "213", "321"])?,
+            Status::Valid
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["3..", "...", "..."])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            symbol_status(&constraints, &["123", "...", "..."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_given_shade() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(2, 2);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Shading { grid });
+        setting.rules.push(settings::Rule::Shading {
+            tokenset,
+            givens: vec![settings::GivenShade {
+                shaded: true,
+                cell: shapes::Cell(1, 0),
+            }],
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(shade_status(&constraints, &[".#", ".."])?, Status::Ongoing);
+        assert_eq!(shade_status(&constraints, &["o#", "#o"])?, Status::Valid);
+        assert_eq!(shade_status(&constraints, &["oo", "#o"])?, Status::Invalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_star_counts() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = star_constraints()?;
+        assert_eq!(
+            shade_status(&constraints, &["....", "....", "....", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            shade_status(&constraints, &["o#oo", "ooo#", "#ooo", "oo#o"])?,
+            Status::Valid
+        );
+        assert_eq!(
+            shade_status(&constraints, &["#o#.", "....", "....", "...."])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            shade_status(&constraints, &["oooo", "....", "....", "...."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_touching_stars() -> Result<(), Box<dyn std::error::Error>> {
+        let constraints = star_constraints()?;
+        assert_eq!(
+            shade_status(&constraints, &["#...", ".o#.", "....", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            shade_status(&constraints, &["#...", "..#.", "....", "...."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            shade_status(&constraints, &["#...", ".#..", "....", "...."])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_loop() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(1, 3);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Edges {
+            grid,
+            lattice: shapes::Lattice::Borders,
+        });
+        setting.rules.push(settings::Rule::SingleLoop { tokenset });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            edge_status(&constraints, &["#..", "#.."], &["#.#."])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            edge_status(&constraints, &["###", "###"], &["#xx#"])?,
+            Status::Valid
+        );
+        assert_eq!(
+            edge_status(&constraints, &["#x#", "#x#"], &["####"])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            edge_status(&constraints, &["#xx", "#xx"], &["##xx"])?,
+            Status::Valid
+        );
+        assert_eq!(
+            edge_status(&constraints, &["xxx", "xxx"], &["xxxx"])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            edge_status(&constraints, &["#..", "x.."], &["#..."])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            edge_status(&constraints, &["##.", "##."], &["#.#."])?,
+            Status::Ongoing
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nurikabe() -> Result<(), Box<dyn std::error::Error>> {
+        let mut setting = settings::PuzzleSetting::default();
+        let grid = setting.add_grid(4, 4);
+        let tokenset = setting.add_tokenset(settings::TokenSet::Shading { grid });
+        setting.rules.push(settings::Rule::Nurikabe {
+            tokenset,
+            clues: vec![
+                settings::IslandClue {
+                    size: 3,
+                    cell: shapes::Cell(1, 1),
+                },
+                settings::IslandClue {
+                    size: 1,
+                    cell: shapes::Cell(3, 3),
+                },
+            ],
+        });
+        let constraints = settings::list_constraints(&setting)?;
+        assert_eq!(
+            shade_status(&constraints, &["....", ".o..", "....", "...o"])?,
+            Status::Ongoing
+        );
+        assert_eq!(
+            shade_status(&constraints, &["####", "#oo#", "#o##", "###o"])?,
+            Status::Valid
+        );
+        assert_eq!(
+            shade_status(&constraints, &["####", "#oo#", "##o#", "###o"])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            shade_status(&constraints, &["####", "#ooo", "#o##", "###o"])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            shade_status(&constraints, &["#o..", "oo..", "...#", "...o"])?,
+            Status::Invalid
+        );
+        assert_eq!(
+            shade_status(&constraints, &["o###", "#o.#", "#.##", "###o"])?,
+            Status::Invalid
+        );
+        Ok(())
+    }
 }
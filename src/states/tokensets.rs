@@ -10,15 +10,57 @@ pub enum CellState {
 
 pub type CellGrid = Vec<Vec<CellState>>;
 
-impl std::ops::Index<&shapes::Cell> for CellGrid {
-    type Output = CellState;
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ShadeState {
+    Shaded,
+    Unshaded,
+    Unknown,
+}
+
+pub type ShadeGrid = Vec<Vec<ShadeState>>;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum EdgeState {
+    On,
+    Off,
+    Unknown,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct EdgeGrid {
+    pub horizontal: Vec<Vec<EdgeState>>,
+    pub vertical: Vec<Vec<EdgeState>>,
+}
+
+impl std::ops::Index<&shapes::Edge> for EdgeGrid {
+    type Output = EdgeState;
+
+    fn index(&self, edge: &shapes::Edge) -> &Self::Output {
+        match *edge {
+            shapes::Edge::Horizontal(x, y) => &self.horizontal[y][x],
+            shapes::Edge::Vertical(x, y) => &self.vertical[y][x],
+        }
+    }
+}
+
+impl std::ops::IndexMut<&shapes::Edge> for EdgeGrid {
+    fn index_mut(&mut self, edge: &shapes::Edge) -> &mut Self::Output {
+        match *edge {
+            shapes::Edge::Horizontal(x, y) => &mut self.horizontal[y][x],
+            shapes::Edge::Vertical(x, y) => &mut self.vertical[y][x],
+        }
+    }
+}
+
+impl<T> std::ops::Index<&shapes::Cell> for Vec<Vec<T>> {
+    type Output = T;
 
     fn index(&self, cell: &shapes::Cell) -> &Self::Output {
         &self[cell.1][cell.0]
     }
 }
 
-impl std::ops::IndexMut<&shapes::Cell> for CellGrid {
+impl<T> std::ops::IndexMut<&shapes::Cell> for Vec<Vec<T>> {
     fn index_mut(&mut self, cell: &shapes::Cell) -> &mut Self::Output {
         &mut self[cell.1][cell.0]
     }
@@ -27,6 +69,8 @@ impl std::ops::IndexMut<&shapes::Cell> for CellGrid {
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum Tokenset {
     Symbols(CellGrid),
+    Shading(ShadeGrid),
+    Edges(EdgeGrid),
 }
 
 pub fn symbolset(grid: &[String], default: CellState) -> Tokenset {
@@ -45,6 +89,44 @@ pub fn symbolset(grid: &[String], default: CellState) -> Tokenset {
     Tokenset::Symbols(cells)
 }
 
+pub fn shadeset(grid: &[String]) -> Tokenset {
+    let cells = grid
+        .iter()
+        .map(|row_contents| {
+            row_contents
+                .chars()
+                .map(|symbol| match symbol {
+                    '#' => ShadeState::Shaded,
+                    'o' => ShadeState::Unshaded,
+                    _ => ShadeState::Unknown,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    Tokenset::Shading(cells)
+}
+
+pub fn edgeset(horizontal: &[String], vertical: &[String]) -> Tokenset {
+    let parse = |rows: &[String]| {
+        rows.iter()
+            .map(|row_contents| {
+                row_contents
+                    .chars()
+                    .map(|symbol| match symbol {
+                        '#' => EdgeState::On,
+                        'x' => EdgeState::Off,
+                        _ => EdgeState::Unknown,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    Tokenset::Edges(EdgeGrid {
+        horizontal: parse(horizontal),
+        vertical: parse(vertical),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;